@@ -0,0 +1,118 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+//!
+//! Cost of polling scope sources once per animation frame.
+//!
+//! Simulates a dozen displays, each fed a 1024-sample block per frame,
+//! and times only the UI side: polling every source and reading its
+//! samples. A second case shows each signal in three displays, each
+//! with its own source, since a source feeds one display. For
+//! comparison it also times the old path, which locked a mutex and
+//! copied each block into a fresh `Arc<Vec<f32>>`.
+//!
+//! Run with `cargo bench --bench scope_poll`.
+
+use std::hint::black_box;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use triple_buffer::triple_buffer;
+use xilem_synth_widgets::{SampleReader, ScopeSource};
+
+const SOURCES: usize = 12;
+const BLOCK: usize = 1024;
+const FRAMES: usize = 20_000;
+/// Displays per signal in the multi-display case.
+const DISPLAYS: usize = 3;
+
+fn main() {
+    let reader = bench_reader(SOURCES);
+    let displays = bench_reader(SOURCES * DISPLAYS);
+    let locked = bench_locked_clone();
+    println!("{SOURCES} sources x {BLOCK} samples, {FRAMES} frames");
+    report("ScopeReader::poll", reader, SOURCES);
+    report(&format!("{DISPLAYS} displays/signal"), displays, SOURCES * DISPLAYS);
+    report("lock + clone (old)", locked, SOURCES);
+}
+
+fn report(name: &str, total: Duration, sources: usize) {
+    let per_frame = total.as_nanos() as f64 / FRAMES as f64;
+    println!(
+        "{name:<20} {:>9.0} ns/frame {:>7.0} ns/source",
+        per_frame,
+        per_frame / sources as f64,
+    );
+}
+
+/// Read `count` triple buffers in place, one reader per source. The
+/// publishing side is not timed, so a signal shown in several displays
+/// costs the same as that many independent signals.
+fn bench_reader(count: usize) -> Duration {
+    let (mut inputs, sources): (Vec<_>, Vec<_>) = (0..count)
+        .map(|_| {
+            let (input, output) = triple_buffer(&vec![0.0_f32; BLOCK]);
+            (input, ScopeSource::new(output))
+        })
+        .unzip();
+    let mut readers: Vec<_> = sources
+        .iter()
+        .map(|source| source.reader().expect("each source has one display"))
+        .collect();
+
+    let mut total = Duration::ZERO;
+    for frame in 0..FRAMES {
+        publish_all(&mut inputs, frame);
+        let start = Instant::now();
+        for reader in &mut readers {
+            if let Some(samples) = reader.poll() {
+                black_box(consume(samples));
+            }
+        }
+        total += start.elapsed();
+    }
+    total
+}
+
+/// The former `ScopeSource::poll`: lock, then copy into a new `Arc`.
+fn bench_locked_clone() -> Duration {
+    let (mut inputs, outputs): (Vec<_>, Vec<_>) = (0..SOURCES)
+        .map(|_| {
+            let (input, output) = triple_buffer(&vec![0.0_f32; BLOCK]);
+            (input, Arc::new(Mutex::new(output)))
+        })
+        .unzip();
+
+    let mut total = Duration::ZERO;
+    for frame in 0..FRAMES {
+        publish_all(&mut inputs, frame);
+        let start = Instant::now();
+        for output in &outputs {
+            let mut out = output.lock().unwrap();
+            if out.update() {
+                let samples = Arc::new(out.peek_output_buffer().clone());
+                black_box(consume(&samples));
+            }
+        }
+        total += start.elapsed();
+    }
+    total
+}
+
+/// Stand-in for the DSP thread: publish one block per source.
+fn publish_all(inputs: &mut [triple_buffer::Input<Vec<f32>>], frame: usize) {
+    for input in inputs {
+        let buf = input.input_buffer_mut();
+        buf.clear();
+        buf.extend((0..BLOCK).map(|i| ((frame + i) as f32 * 0.01).sin()));
+        input.publish();
+    }
+}
+
+/// Touch every sample, as ingesting does.
+fn consume(samples: &[f32]) -> f32 {
+    samples.iter().copied().fold(0.0, f32::max)
+}
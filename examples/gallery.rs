@@ -23,8 +23,8 @@ use xilem::view::{flex_col, flex_row, label, task, FlexExt as _, FlexSpacer};
 use xilem::{EventLoop, WidgetView, WindowOptions, Xilem};
 
 use xilem_synth_widgets::{
-    fader, group_box, knob, led, level_meter, param_selector, push_button, scope,
-    LabelAlign, ScopeSource,
+    fader, group_box, knob, led, level_meter, param_selector, push_button, scope, slider,
+    ring_buffer, spectrogram, spectrum, Colormap, LabelAlign, ScopeSource,
 };
 
 // ── colors ──────────────────────────────────────────────────────────────
@@ -49,6 +49,9 @@ const SKY: Color = Color::from_rgb8(0x70, 0xC0, 0xE8);
 struct GalleryState {
     phase: f64,
     scope_source: ScopeSource,
+    spectrum_source: ScopeSource,
+    spectrogram_source: ScopeSource,
+    meter_source: ScopeSource,
     _anim_running: Arc<AtomicBool>,
 
     // Interactive widget state
@@ -56,6 +59,8 @@ struct GalleryState {
     knob_b: f64,
     knob_c: f64,
     fader_val: f64,
+    mix: f64,
+    pan: f64,
     selector_idx: usize,
     bool_idx: usize,
     push_a: bool,
@@ -67,28 +72,51 @@ impl GalleryState {
     fn new() -> Self {
         let (scope_input, scope_output) = triple_buffer::triple_buffer(&vec![0.0f32; 1024]);
         let scope_source = ScopeSource::new(scope_output);
+        let (spectrum_input, spectrum_output) = triple_buffer::triple_buffer(&vec![0.0f32; 1024]);
+        let spectrum_source = ScopeSource::new(spectrum_output);
+        // The spectrogram and the signal meter get every sample through rings
+        let (spectrogram_input, spectrogram_output) = ring_buffer(8192, 1);
+        let spectrogram_source = ScopeSource::from_reader(spectrogram_output);
+        let (meter_input, meter_output) = ring_buffer(8192, 1);
+        let meter_source = ScopeSource::from_reader(meter_output);
         let running = Arc::new(AtomicBool::new(true));
 
-        // Background thread generates a sine wave into the scope
+        // Background thread generates a sine wave into the scope and,
+        // with a few added harmonics, into the spectrum and spectrogram.
+        // The meter gets the harmonic signal with a slow swell.
         let running_clone = Arc::clone(&running);
         let mut input = scope_input;
+        let mut spectrum_input = spectrum_input;
+        let mut spectrogram_input = spectrogram_input;
+        let mut meter_input = meter_input;
         std::thread::spawn(move || {
             let sample_rate = 44100.0_f64;
             let freq = 220.0;
             let buf_size = 1024;
             let mut phase = 0.0_f64;
             let phase_inc = freq / sample_rate;
+            let mut blocks = 0_u32;
 
             while running_clone.load(Ordering::Relaxed) {
                 let mut buf = vec![0.0f32; buf_size];
-                for s in buf.iter_mut() {
-                    *s = (phase * TAU).sin() as f32;
+                let mut rich = vec![0.0f32; buf_size];
+                for (s, r) in buf.iter_mut().zip(rich.iter_mut()) {
+                    let p = phase * TAU;
+                    *s = p.sin() as f32;
+                    *r = (0.5 * p.sin() + 0.2 * (3.0 * p).sin() + 0.05 * (7.0 * p).sin()) as f32;
                     phase += phase_inc;
                     if phase >= 1.0 {
                         phase -= 1.0;
                     }
                 }
+                // Peaks just over full scale now and then, to light the clip indicator
+                let swell = (0.7 + 0.7 * (blocks as f64 * 0.05).sin()) as f32;
+                let swelled: Vec<f32> = rich.iter().map(|s| s * swell).collect();
+                blocks = blocks.wrapping_add(1);
                 input.write(buf);
+                spectrogram_input.push(&rich);
+                meter_input.push(&swelled);
+                spectrum_input.write(rich);
                 std::thread::sleep(Duration::from_millis(16));
             }
         });
@@ -96,11 +124,16 @@ impl GalleryState {
         Self {
             phase: 0.0,
             scope_source,
+            spectrum_source,
+            spectrogram_source,
+            meter_source,
             _anim_running: running,
             knob_a: 0.5,
             knob_b: 220.0,
             knob_c: 0.75,
             fader_val: -12.0,
+            mix: 50.0,
+            pan: 0.0,
             selector_idx: 0,
             bool_idx: 0,
             push_a: false,
@@ -239,6 +272,33 @@ fn app_logic(state: &mut GalleryState) -> impl WidgetView<GalleryState> + use<>
                     )
                     .tint(CHARCOAL),
 
+                    // Sliders
+                    group_box(
+                        "Sliders",
+                        flex_col((
+                            slider(0.0, 100.0, state.mix, 50.0,
+                                |s: &mut GalleryState, v| { s.mix = v; })
+                                .step(1.0)
+                                .tick_label(0.0, "Dry")
+                                .tick(50.0)
+                                .tick_label(100.0, "Wet")
+                                .readout(|v| format!("{v:.0} %"))
+                                .length(110.0)
+                                .tint(TEAL),
+                            label("Mix").text_size(9.0).color(DIM),
+                            slider(-1.0, 1.0, state.pan, 0.0,
+                                |s: &mut GalleryState, v| { s.pan = v; })
+                                .bipolar()
+                                .tick_label(-1.0, "L")
+                                .tick_label(1.0, "R")
+                                .horizontal()
+                                .length(110.0),
+                            label("Pan").text_size(9.0).color(DIM),
+                        ))
+                        .gap(2.0.px()),
+                    )
+                    .tint(CHARCOAL),
+
                     // Selector & Bool
                     group_box(
                         "Selectors",
@@ -310,6 +370,24 @@ fn app_logic(state: &mut GalleryState) -> impl WidgetView<GalleryState> + use<>
                         scope(Some(state.scope_source.clone())),
                     )
                     .tint(CHARCOAL),
+
+                    // Spectrum
+                    group_box::<GalleryState, (), _>(
+                        "Spectrum",
+                        spectrum(Some(state.spectrum_source.clone()))
+                            .sample_rate(44_100.0)
+                            .peak_hold(Duration::from_secs(1)),
+                    )
+                    .tint(CHARCOAL),
+
+                    // Spectrogram
+                    group_box::<GalleryState, (), _>(
+                        "Spectrogram",
+                        spectrogram(Some(state.spectrogram_source.clone()))
+                            .sample_rate(44_100.0)
+                            .colormap(Colormap::Viridis),
+                    )
+                    .tint(CHARCOAL),
                 ))
                 .cross_axis_alignment(CrossAxisAlignment::Stretch)
                 .gap(4.0.px()),
@@ -327,6 +405,14 @@ fn app_logic(state: &mut GalleryState) -> impl WidgetView<GalleryState> + use<>
                                 label("H").text_size(9.0).color(DIM),
                                 level_meter(meter_b, -60.0, 6.0),
                             )).gap(4.0.px()),
+                            flex_row((
+                                label("S").text_size(9.0).color(DIM),
+                                level_meter(-60.0, -60.0, 6.0)
+                                    .source(state.meter_source.clone())
+                                    .ballistics(Duration::from_millis(10), Duration::from_millis(300))
+                                    .peak_hold(Duration::from_millis(1500), 20.0)
+                                    .clip(0.0),
+                            )).gap(4.0.px()),
                             FlexSpacer::Fixed(4.0.px()),
                             flex_row((
                                 level_meter(meter_c, -60.0, 6.0).vertical(),
@@ -0,0 +1,268 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use std::f32::consts::PI;
+
+/// Smallest and largest supported FFT sizes.
+pub(crate) const MIN_FFT_SIZE: usize = 64;
+pub(crate) const MAX_FFT_SIZE: usize = 32_768;
+/// Floor for magnitudes, so silence maps to a finite dB value.
+const MIN_DB: f32 = -200.0;
+
+/// Window applied to each block before the FFT.
+///
+/// Windowing tapers the block edges to zero, trading frequency
+/// resolution for lower leakage between bins.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum WindowFunction {
+    /// Good general-purpose choice: narrow peaks, moderate leakage.
+    #[default]
+    Hann,
+    /// Very low leakage (-92 dB sidelobes) with wider peaks. Use it to
+    /// see quiet partials next to loud ones.
+    BlackmanHarris,
+}
+
+impl WindowFunction {
+    fn coefficients(self, size: usize) -> Vec<f32> {
+        let n = size as f32;
+        (0..size)
+            .map(|i| {
+                let x = 2.0 * PI * i as f32 / n;
+                match self {
+                    WindowFunction::Hann => 0.5 - 0.5 * x.cos(),
+                    WindowFunction::BlackmanHarris => {
+                        0.35875 - 0.48829 * x.cos() + 0.14128 * (2.0 * x).cos()
+                            - 0.01168 * (3.0 * x).cos()
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// How an analyzer treats the channels of a multi-channel source.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum ChannelMode {
+    /// Average the channels into one signal and analyze that. Cheapest,
+    /// but content that is out of phase between channels cancels.
+    #[default]
+    Mix,
+    /// Analyze each channel on its own and show the loudest one in
+    /// every bin, so nothing cancels. Costs one FFT per channel.
+    Max,
+}
+
+/// Append one interleaved frame to the signals an analyzer keeps: the
+/// channels' average for [`ChannelMode::Mix`], each channel to its own
+/// signal for [`ChannelMode::Max`]. Starts afresh if the number of
+/// signals changes.
+pub(crate) fn push_frame(frame: &[f32], mode: ChannelMode, signals: &mut Vec<Vec<f32>>) {
+    let count = match mode {
+        ChannelMode::Mix => 1,
+        ChannelMode::Max => frame.len(),
+    };
+    if signals.len() != count {
+        signals.clear();
+        signals.resize_with(count, Vec::new);
+    }
+    match mode {
+        ChannelMode::Mix => signals[0].push(frame.iter().sum::<f32>() / frame.len() as f32),
+        ChannelMode::Max => {
+            for (signal, &sample) in signals.iter_mut().zip(frame) {
+                signal.push(sample);
+            }
+        }
+    }
+}
+
+/// Round a requested FFT size to a supported power of two.
+pub(crate) fn fft_size(size: usize) -> usize {
+    size.clamp(MIN_FFT_SIZE, MAX_FFT_SIZE).next_power_of_two()
+}
+
+/// In-place iterative radix-2 FFT of a fixed power-of-two size.
+pub(crate) struct Fft {
+    size: usize,
+    /// `e^(-2πik/size)` for `k` in `0..size/2`
+    twiddles: Vec<(f32, f32)>,
+}
+
+impl Fft {
+    pub(crate) fn new(size: usize) -> Self {
+        debug_assert!(size.is_power_of_two());
+        let twiddles = (0..size / 2)
+            .map(|k| {
+                let angle = -2.0 * PI * k as f32 / size as f32;
+                (angle.cos(), angle.sin())
+            })
+            .collect();
+        Self { size, twiddles }
+    }
+
+    /// Transform `re` + i·`im` in place. Both slices must hold `size` values.
+    pub(crate) fn process(&self, re: &mut [f32], im: &mut [f32]) {
+        let n = self.size;
+        debug_assert!(re.len() == n && im.len() == n);
+
+        // Bit-reversal permutation
+        let bits = n.trailing_zeros();
+        for i in 0..n {
+            let j = i.reverse_bits() >> (usize::BITS - bits);
+            if j > i {
+                re.swap(i, j);
+                im.swap(i, j);
+            }
+        }
+
+        // Butterflies, doubling the span each pass
+        let mut span = 1;
+        while span < n {
+            let stride = n / (span * 2);
+            for start in (0..n).step_by(span * 2) {
+                for k in 0..span {
+                    let (wr, wi) = self.twiddles[k * stride];
+                    let (a, b) = (start + k, start + k + span);
+                    let tr = re[b] * wr - im[b] * wi;
+                    let ti = re[b] * wi + im[b] * wr;
+                    re[b] = re[a] - tr;
+                    im[b] = im[a] - ti;
+                    re[a] += tr;
+                    im[a] += ti;
+                }
+            }
+            span *= 2;
+        }
+    }
+}
+
+/// Windowed magnitude spectrum of real blocks, in dBFS.
+///
+/// Scaled so that a full-scale sine on a bin centre reads 0 dB
+/// whatever the window.
+pub(crate) struct SpectrumAnalyzer {
+    fft: Fft,
+    window: Vec<f32>,
+    /// Amplitude scale undoing the window's coherent gain
+    scale: f32,
+    re: Vec<f32>,
+    im: Vec<f32>,
+    /// One input's magnitudes while combining several
+    scratch: Vec<f32>,
+}
+
+impl SpectrumAnalyzer {
+    pub(crate) fn new(size: usize, window: WindowFunction) -> Self {
+        let coefficients = window.coefficients(size);
+        let gain: f32 = coefficients.iter().sum();
+        Self {
+            fft: Fft::new(size),
+            window: coefficients,
+            scale: 2.0 / gain,
+            re: vec![0.0; size],
+            im: vec![0.0; size],
+            scratch: vec![0.0; size / 2 + 1],
+        }
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        self.fft.size
+    }
+
+    /// Number of bins produced, DC through Nyquist.
+    pub(crate) fn bins(&self) -> usize {
+        self.fft.size / 2 + 1
+    }
+
+    /// Analyze the last `size` samples of `input` (zero-padded in front
+    /// when shorter) into `out`, which must hold [`Self::bins`] values.
+    pub(crate) fn magnitudes_db(&mut self, input: &[f32], out: &mut [f32]) {
+        let n = self.fft.size;
+        let take = input.len().min(n);
+        let pad = n - take;
+        self.re[..pad].fill(0.0);
+        for (i, &x) in input[input.len() - take..].iter().enumerate() {
+            self.re[pad + i] = x * self.window[pad + i];
+        }
+        self.im.fill(0.0);
+        self.fft.process(&mut self.re, &mut self.im);
+
+        for (k, db) in out.iter_mut().enumerate().take(n / 2 + 1) {
+            let mag = (self.re[k] * self.re[k] + self.im[k] * self.im[k]).sqrt() * self.scale;
+            *db = (20.0 * mag.log10()).max(MIN_DB);
+        }
+    }
+
+    /// Like [`Self::magnitudes_db`] for several inputs, such as the
+    /// channels of a source, keeping the loudest value in each bin.
+    pub(crate) fn max_magnitudes_db(&mut self, inputs: &[Vec<f32>], out: &mut [f32]) {
+        if let [input] = inputs {
+            self.magnitudes_db(input, out);
+            return;
+        }
+        out.fill(MIN_DB);
+        let mut scratch = std::mem::take(&mut self.scratch);
+        for input in inputs {
+            self.magnitudes_db(input, &mut scratch);
+            for (db, &channel_db) in out.iter_mut().zip(&scratch) {
+                *db = db.max(channel_db);
+            }
+        }
+        self.scratch = scratch;
+    }
+}
+
+/// Level of a display band spanning fractional bins `b0..b1`: the
+/// loudest bin when it covers several, otherwise interpolated.
+pub(crate) fn band_db(values: &[f32], b0: f64, b1: f64) -> f32 {
+    let last = values.len().saturating_sub(1);
+    if b1 - b0 >= 1.0 {
+        let lo = (b0.ceil() as usize).min(last);
+        let hi = (b1.floor() as usize).clamp(lo, last);
+        return values[lo..=hi].iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    }
+    let pos = ((b0 + b1) / 2.0).clamp(0.0, last as f64);
+    let i = pos.floor() as usize;
+    let frac = (pos - i as f64) as f32;
+    let a = values[i];
+    let b = values[(i + 1).min(last)];
+    if a.is_finite() && b.is_finite() { a + (b - a) * frac } else { a.max(b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: usize = 1024;
+    const BIN: usize = 64;
+
+    /// A full-scale sine centred on `BIN`.
+    fn sine() -> Vec<f32> {
+        (0..SIZE).map(|i| (2.0 * PI * ((BIN * i) % SIZE) as f32 / SIZE as f32).sin()).collect()
+    }
+
+    #[test]
+    fn full_scale_sine_reads_zero_db() {
+        for window in [WindowFunction::Hann, WindowFunction::BlackmanHarris] {
+            let mut analyzer = SpectrumAnalyzer::new(SIZE, window);
+            let mut out = vec![0.0; analyzer.bins()];
+            analyzer.magnitudes_db(&sine(), &mut out);
+            assert!(out[BIN].abs() < 0.01, "{window:?}: {} dB", out[BIN]);
+            assert!(out[BIN * 4] < -60.0, "{window:?}: leakage {} dB", out[BIN * 4]);
+        }
+    }
+
+    #[test]
+    fn max_keeps_out_of_phase_channels() {
+        let left = sine();
+        let right: Vec<f32> = left.iter().map(|x| -x).collect();
+        let mut analyzer = SpectrumAnalyzer::new(SIZE, WindowFunction::Hann);
+        let mut out = vec![0.0; analyzer.bins()];
+        analyzer.max_magnitudes_db(&[left, right], &mut out);
+        assert!(out[BIN].abs() < 0.01, "{} dB", out[BIN]);
+    }
+}
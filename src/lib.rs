@@ -8,15 +8,32 @@
 pub mod widgets;
 mod views;
 pub mod theme;
+pub mod taper;
+mod fft;
+mod ring;
 
 pub use views::fader::fader;
 pub use views::group_box::group_box;
 pub use views::knob::knob;
 pub use views::param_selector::{param_selector, LabelAlign};
 pub use views::push_button::push_button;
-pub use views::scope::{scope, ScopeBuffer, ScopeSource};
+pub use views::scope::{scope, SampleReader, ScopeBuffer, ScopeReader, ScopeSource, SourceInUse};
+pub use ring::{ring_buffer, RingConsumer, RingProducer};
+pub use views::slider::slider;
+pub use widgets::scope::{DisplayMode, TraceLayout, TriggerEdge, TriggerMode};
+pub use views::spectrum::spectrum;
+pub use views::spectrogram::spectrogram;
+pub use widgets::spectrogram::Colormap;
+pub use fft::{ChannelMode, WindowFunction};
 pub use views::led::led;
 pub use views::level_meter::level_meter;
 pub use widgets::level_meter::{MeterScale, MeterStyle, Orientation};
+pub use taper::{Taper, TaperCurve};
+pub use views::gesture::{GestureHandler, NoGesture};
+pub use widgets::entry::EntryGesture;
+pub use widgets::gesture::ControlAction;
+pub use widgets::readout::ValueFormatter;
+pub use widgets::slider::SliderTick;
+pub use xilem::masonry::core::keyboard::Modifiers;
 
 pub use xilem;
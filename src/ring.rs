@@ -0,0 +1,210 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+
+use crate::widgets::scope::SampleReader;
+
+/// Shared storage of a sample ring.
+///
+/// Samples are kept as `f32` bits in atomics, so neither side needs
+/// `unsafe` or a lock. The positions only ever grow (wrapping), and
+/// each is written by one side only.
+struct Ring {
+    data: Box<[AtomicU32]>,
+    /// `data.len() - 1`; the length is a power of two
+    mask: usize,
+    /// Total samples written, advanced by the producer
+    write: AtomicUsize,
+    /// Total samples read, advanced by the consumer
+    read: AtomicUsize,
+    /// Total samples dropped because the ring was full
+    dropped: AtomicU64,
+}
+
+/// Create a lock-free single-producer, single-consumer sample ring.
+///
+/// Unlike a triple buffer, which only hands over the latest block, the
+/// ring delivers every sample in order, so rolling displays and
+/// analyzers see a gapless stream. `capacity` (rounded up to a power of
+/// two) should cover a few animation frames of audio, e.g. 8192 samples
+/// per channel at 48 kHz.
+///
+/// ```ignore
+/// let (mut producer, consumer) = ring_buffer(16_384, 2);
+/// // DSP thread: producer.push(&interleaved_block);
+/// let source = ScopeSource::from_reader(consumer);
+/// ```
+pub fn ring_buffer(capacity: usize, channels: usize) -> (RingProducer, RingConsumer) {
+    let channels = channels.max(1);
+    let len = capacity.max(channels).next_power_of_two();
+    let ring = Arc::new(Ring {
+        data: (0..len).map(|_| AtomicU32::new(0)).collect(),
+        mask: len - 1,
+        write: AtomicUsize::new(0),
+        read: AtomicUsize::new(0),
+        dropped: AtomicU64::new(0),
+    });
+    let producer = RingProducer { ring: Arc::clone(&ring), channels };
+    let consumer = RingConsumer {
+        ring,
+        channels,
+        scratch: Vec::with_capacity(len),
+        seen_dropped: 0,
+        gap: false,
+    };
+    (producer, consumer)
+}
+
+/// Writing end of a [`ring_buffer`], owned by the DSP thread.
+///
+/// Real-time safe: pushing never locks, allocates or waits. When the
+/// consumer falls behind, samples that do not fit are dropped.
+pub struct RingProducer {
+    ring: Arc<Ring>,
+    channels: usize,
+}
+
+impl RingProducer {
+    /// Append interleaved samples. Only whole frames are written; returns
+    /// the number of samples that fit.
+    pub fn push(&mut self, samples: &[f32]) -> usize {
+        let ring = &*self.ring;
+        let write = ring.write.load(Ordering::Relaxed);
+        let read = ring.read.load(Ordering::Acquire);
+        let free = ring.data.len() - write.wrapping_sub(read);
+        let mut n = samples.len().min(free);
+        n -= n % self.channels;
+        for (i, &sample) in samples[..n].iter().enumerate() {
+            let slot = &ring.data[write.wrapping_add(i) & ring.mask];
+            slot.store(sample.to_bits(), Ordering::Relaxed);
+        }
+        ring.write.store(write.wrapping_add(n), Ordering::Release);
+        if n < samples.len() {
+            ring.dropped.fetch_add((samples.len() - n) as u64, Ordering::Release);
+        }
+        n
+    }
+
+    /// Total samples dropped because the ring was full.
+    pub fn dropped(&self) -> u64 {
+        self.ring.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Reading end of a [`ring_buffer`].
+///
+/// Each poll drains everything written since the last one into a buffer
+/// allocated up front, so steady-state polling does not allocate.
+///
+/// Samples are only dropped while the ring is full, so they are missing
+/// after the block a poll returns. The next poll's block then doesn't
+/// continue it, which [`is_gapless`](SampleReader::is_gapless) reports.
+pub struct RingConsumer {
+    ring: Arc<Ring>,
+    channels: usize,
+    scratch: Vec<f32>,
+    /// Producer's drop count at the last poll
+    seen_dropped: u64,
+    /// Samples were dropped after the last poll's block
+    gap: bool,
+}
+
+impl RingConsumer {
+    /// Total samples the producer dropped because the ring was full.
+    pub fn dropped(&self) -> u64 {
+        self.ring.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl SampleReader for RingConsumer {
+    fn channels(&self) -> usize {
+        self.channels
+    }
+
+    fn poll(&mut self) -> Option<&[f32]> {
+        let ring = &*self.ring;
+        let read = ring.read.load(Ordering::Relaxed);
+        let write = ring.write.load(Ordering::Acquire);
+        let dropped = ring.dropped.load(Ordering::Acquire);
+        self.gap = dropped != self.seen_dropped;
+        self.seen_dropped = dropped;
+        let n = write.wrapping_sub(read);
+        if n == 0 {
+            return None;
+        }
+        self.scratch.clear();
+        self.scratch.extend((0..n).map(|i| {
+            f32::from_bits(ring.data[read.wrapping_add(i) & ring.mask].load(Ordering::Relaxed))
+        }));
+        ring.read.store(write, Ordering::Release);
+        Some(&self.scratch)
+    }
+
+    fn is_gapless(&self) -> bool {
+        !self.gap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(start: usize, len: usize) -> Vec<f32> {
+        (start..start + len).map(|i| i as f32).collect()
+    }
+
+    #[test]
+    fn wraps_around_in_order() {
+        let (mut producer, mut consumer) = ring_buffer(8, 1);
+        for round in 0..5 {
+            let block = ramp(round * 6, 6);
+            assert_eq!(producer.push(&block), 6);
+            assert_eq!(consumer.poll(), Some(block.as_slice()));
+            assert!(consumer.is_gapless());
+        }
+        assert_eq!(consumer.poll(), None);
+        assert_eq!(consumer.dropped(), 0);
+    }
+
+    #[test]
+    fn writes_whole_frames_when_channels_do_not_divide_capacity() {
+        // 16 slots hold 5 frames of 3 channels, with one slot spare
+        let (mut producer, mut consumer) = ring_buffer(16, 3);
+        assert_eq!(producer.push(&ramp(0, 18)), 15);
+        assert_eq!(producer.dropped(), 3);
+        assert_eq!(consumer.poll(), Some(ramp(0, 15).as_slice()));
+        // A trailing partial frame is dropped too
+        assert_eq!(producer.push(&ramp(100, 7)), 6);
+        assert_eq!(producer.dropped(), 4);
+        assert_eq!(consumer.poll(), Some(ramp(100, 6).as_slice()));
+        // Written from slot 5 on, these frames wrap past the end of the storage
+        let wrapped = ramp(200, 12);
+        assert_eq!(producer.push(&wrapped), 12);
+        assert_eq!(consumer.poll(), Some(wrapped.as_slice()));
+    }
+
+    #[test]
+    fn overflow_drops_newest_and_flags_gap() {
+        let (mut producer, mut consumer) = ring_buffer(8, 2);
+        assert_eq!(producer.push(&ramp(0, 6)), 6);
+        // Only one more frame fits
+        assert_eq!(producer.push(&ramp(6, 6)), 2);
+        assert_eq!(producer.dropped(), 4);
+        assert_eq!(consumer.dropped(), 4);
+
+        // The block read is intact; the gap follows it
+        assert!(consumer.is_gapless());
+        assert_eq!(consumer.poll(), Some(ramp(0, 8).as_slice()));
+        assert!(!consumer.is_gapless());
+
+        assert_eq!(producer.push(&ramp(20, 4)), 4);
+        assert_eq!(consumer.poll(), Some(ramp(20, 4).as_slice()));
+        assert!(consumer.is_gapless());
+    }
+}
@@ -0,0 +1,204 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use std::fmt;
+use std::sync::Arc;
+
+/// A user-supplied mapping between parameter values and control position.
+///
+/// Implement this for curves the built-in [`Taper`] variants don't cover.
+/// Both directions must be monotonic and inverse to each other.
+pub trait TaperCurve: Send + Sync {
+    /// Map a value in `min..=max` to a position in `0..=1`.
+    fn to_normalized(&self, value: f64, min: f64, max: f64) -> f64;
+    /// Map a position in `0..=1` back to a value in `min..=max`.
+    fn from_normalized(&self, norm: f64, min: f64, max: f64) -> f64;
+}
+
+/// How a control's travel is distributed over its value range.
+///
+/// The control position (indicator angle, lit arc, drag distance) is
+/// always linear; the taper decides which value sits at each position.
+#[derive(Clone, Default)]
+pub enum Taper {
+    /// Equal travel covers equal value distance.
+    #[default]
+    Linear,
+    /// Equal travel covers equal value *ratios* (e.g. octaves).
+    /// Use for frequency, time and Q. Requires `min` and `max` to be
+    /// positive; otherwise behaves like `Linear`.
+    Log,
+    /// Power-law skew: `value = min + (max - min) * position^exponent`.
+    /// Exponents above 1 give more resolution near `min`, below 1 near `max`.
+    Exponential(f64),
+    /// A custom curve.
+    Custom(Arc<dyn TaperCurve>),
+}
+
+impl Taper {
+    /// Wrap a custom curve.
+    pub fn custom(curve: impl TaperCurve + 'static) -> Self {
+        Taper::Custom(Arc::new(curve))
+    }
+
+    /// Map a value in `min..=max` to a position in `0..=1`.
+    pub fn to_normalized(&self, value: f64, min: f64, max: f64) -> f64 {
+        let range = max - min;
+        if range.abs() < f64::EPSILON {
+            return 0.0;
+        }
+        let value = value.clamp(min.min(max), min.max(max));
+        let norm = match self {
+            Taper::Log if min > 0.0 && max > 0.0 => (value / min).ln() / (max / min).ln(),
+            Taper::Linear | Taper::Log => (value - min) / range,
+            Taper::Exponential(exp) => {
+                let lin = (value - min) / range;
+                if *exp > 0.0 { lin.powf(1.0 / exp) } else { lin }
+            }
+            Taper::Custom(curve) => curve.to_normalized(value, min, max),
+        };
+        norm.clamp(0.0, 1.0)
+    }
+
+    /// Map a position in `0..=1` back to a value in `min..=max`.
+    pub fn from_normalized(&self, norm: f64, min: f64, max: f64) -> f64 {
+        let norm = norm.clamp(0.0, 1.0);
+        match self {
+            Taper::Log if min > 0.0 && max > 0.0 => min * (max / min).powf(norm),
+            Taper::Linear | Taper::Log => min + norm * (max - min),
+            Taper::Exponential(exp) => {
+                let lin = if *exp > 0.0 { norm.powf(*exp) } else { norm };
+                min + lin * (max - min)
+            }
+            Taper::Custom(curve) => curve.from_normalized(norm, min, max),
+        }
+    }
+}
+
+impl PartialEq for Taper {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Taper::Linear, Taper::Linear) | (Taper::Log, Taper::Log) => true,
+            (Taper::Exponential(a), Taper::Exponential(b)) => a == b,
+            (Taper::Custom(a), Taper::Custom(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Debug for Taper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Taper::Linear => f.write_str("Linear"),
+            Taper::Log => f.write_str("Log"),
+            Taper::Exponential(exp) => f.debug_tuple("Exponential").field(exp).finish(),
+            Taper::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Quadratic curve, to exercise `Taper::Custom`.
+    struct Square;
+
+    impl TaperCurve for Square {
+        fn to_normalized(&self, value: f64, min: f64, max: f64) -> f64 {
+            ((value - min) / (max - min)).sqrt()
+        }
+
+        fn from_normalized(&self, norm: f64, min: f64, max: f64) -> f64 {
+            min + norm * norm * (max - min)
+        }
+    }
+
+    fn tapers() -> Vec<Taper> {
+        vec![
+            Taper::Linear,
+            Taper::Log,
+            Taper::Exponential(2.0),
+            Taper::Exponential(0.5),
+            Taper::custom(Square),
+        ]
+    }
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} != {b}");
+    }
+
+    #[test]
+    fn round_trips() {
+        for taper in tapers() {
+            for (min, max) in [(20.0, 20_000.0), (0.1, 10.0), (20_000.0, 20.0)] {
+                for i in 0..=20 {
+                    let norm = i as f64 / 20.0;
+                    let value = taper.from_normalized(norm, min, max);
+                    assert_close(taper.to_normalized(value, min, max), norm);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn ends_map_to_the_range_ends() {
+        for taper in tapers() {
+            for (min, max) in [(20.0, 20_000.0), (20_000.0, 20.0)] {
+                assert_close(taper.from_normalized(0.0, min, max), min);
+                assert_close(taper.from_normalized(1.0, min, max), max);
+                assert_close(taper.to_normalized(min, min, max), 0.0);
+                assert_close(taper.to_normalized(max, min, max), 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn out_of_range_inputs_are_clamped() {
+        for taper in tapers() {
+            assert_close(taper.from_normalized(-0.5, 1.0, 100.0), 1.0);
+            assert_close(taper.from_normalized(1.5, 1.0, 100.0), 100.0);
+            assert_close(taper.to_normalized(0.5, 1.0, 100.0), 0.0);
+            assert_close(taper.to_normalized(200.0, 1.0, 100.0), 1.0);
+            // Reversed ranges clamp the same way
+            assert_close(taper.to_normalized(0.5, 100.0, 1.0), 1.0);
+            assert_close(taper.to_normalized(200.0, 100.0, 1.0), 0.0);
+        }
+    }
+
+    #[test]
+    fn reversed_range_runs_high_to_low() {
+        let linear = Taper::Linear;
+        assert_close(linear.from_normalized(0.25, 10.0, 0.0), 7.5);
+        assert_close(linear.to_normalized(7.5, 10.0, 0.0), 0.25);
+        // Log keeps equal ratios per travel going downwards too
+        let log = Taper::Log;
+        assert_close(log.from_normalized(0.5, 1000.0, 10.0), 100.0);
+        assert_close(log.to_normalized(100.0, 1000.0, 10.0), 0.5);
+    }
+
+    #[test]
+    fn log_with_non_positive_range_is_linear() {
+        for (min, max) in [(-10.0, 10.0), (0.0, 10.0)] {
+            for i in 0..=4 {
+                let norm = i as f64 / 4.0;
+                assert_close(
+                    Taper::Log.from_normalized(norm, min, max),
+                    Taper::Linear.from_normalized(norm, min, max),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn empty_range_is_at_the_start() {
+        for taper in tapers() {
+            assert_close(taper.to_normalized(5.0, 5.0, 5.0), 0.0);
+            assert_close(taper.from_normalized(0.7, 5.0, 5.0), 5.0);
+        }
+    }
+}
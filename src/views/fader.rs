@@ -5,25 +5,43 @@
 //! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
 //! (compatible with the Xilem licence).
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use xilem::core::{MessageCtx, Mut, View, ViewMarker};
 use xilem::core::MessageResult;
 use xilem::{Pod, ViewCtx};
 
+use crate::views::gesture::{GestureHandler, NoGesture};
 use crate::widgets::fader::Fader as FaderWidget;
+use crate::widgets::entry::EntryGesture;
+use crate::widgets::gesture::ControlAction;
+use crate::widgets::level_meter::Orientation;
+use crate::widgets::readout::ValueFormatter;
 
-/// A vertical fader view with logarithmic scale for volume control.
+/// A fader view with logarithmic scale for volume control.
 ///
-/// Emits dB values. Drag vertically to adjust.
-pub struct Fader<F> {
+/// Emits dB values. Drag along the track to adjust, or use the keyboard
+/// when focused. `on_gesture_begin` / `on_gesture_end` report when the
+/// user grabs and releases the fader, for automation recording.
+pub struct Fader<F, B = NoGesture, E = NoGesture> {
     min_db: f64,
     max_db: f64,
     value_db: f64,
     default_db: f64,
     on_change: F,
+    on_gesture_begin: B,
+    on_gesture_end: E,
     tint: Option<xilem::Color>,
+    orientation: Orientation,
+    length: Option<f64>,
+    jump_to_click: bool,
+    glide: Option<Duration>,
+    readout: Option<ValueFormatter>,
+    entry_gesture: EntryGesture,
 }
 
-/// Create a vertical fader. Values are in dB. Typical range: -60.0 to 6.0.
+/// Create a fader, vertical by default. Values are in dB. Typical range: -60.0 to 6.0.
 ///
 /// `default_db` is the value restored on double-click.
 pub fn fader<State, Action>(
@@ -33,30 +51,122 @@ pub fn fader<State, Action>(
     default_db: f64,
     on_change: impl Fn(&mut State, f64) -> Action + Send + Sync + 'static,
 ) -> Fader<impl Fn(&mut State, f64) -> Action + Send + Sync + 'static> {
-    Fader { min_db, max_db, value_db, default_db, on_change, tint: None }
+    Fader {
+        min_db, max_db, value_db, default_db, on_change,
+        on_gesture_begin: NoGesture,
+        on_gesture_end: NoGesture,
+        tint: None,
+        orientation: Orientation::Vertical,
+        length: None,
+        jump_to_click: false,
+        glide: None,
+        readout: None,
+        entry_gesture: EntryGesture::Disabled,
+    }
 }
 
-impl<F> Fader<F> {
+impl<F, B, E> Fader<F, B, E> {
     pub fn tint(mut self, color: xilem::Color) -> Self {
         self.tint = Some(color);
         self
     }
+
+    /// Lay the track out left to right, e.g. for crossfaders and pan.
+    pub fn horizontal(mut self) -> Self {
+        self.orientation = Orientation::Horizontal;
+        self
+    }
+
+    /// Preferred track length in pixels. Default 140; the fader shrinks
+    /// when the parent offers less space.
+    pub fn length(mut self, length: f64) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Clicking the track away from the grip moves the grip to the pointer
+    /// instead of starting a relative drag. Clicking the grip still drags.
+    pub fn jump_to_click(mut self) -> Self {
+        self.jump_to_click = true;
+        self
+    }
+
+    /// Jump to the clicked point with an animated glide lasting `time`,
+    /// e.g. `Duration::from_millis(120)`. Implies [`Self::jump_to_click`].
+    pub fn glide(mut self, time: Duration) -> Self {
+        self.jump_to_click = true;
+        self.glide = Some(time);
+        self
+    }
+
+    /// Show the formatted dB value over the grip while hovered or dragged,
+    /// e.g. `.readout(|v| format!("{v:.1} dB"))`.
+    pub fn readout(mut self, formatter: impl Fn(f64) -> String + Send + Sync + 'static) -> Self {
+        self.readout = Some(Arc::new(formatter));
+        self
+    }
+
+    /// Gesture that opens an inline field for typing an exact dB value,
+    /// e.g. `EntryGesture::ModifierClick(Modifiers::ALT)`.
+    pub fn entry_gesture(mut self, gesture: EntryGesture) -> Self {
+        self.entry_gesture = gesture;
+        self
+    }
+
+    /// Called when the user grabs the fader (drag start, or before a
+    /// one-off edit such as a key press, scroll or reset).
+    pub fn on_gesture_begin<State, Action>(
+        self,
+        callback: impl Fn(&mut State) -> Action + Send + Sync + 'static,
+    ) -> Fader<F, impl Fn(&mut State) -> Action + Send + Sync + 'static, E> {
+        let Fader {
+            min_db, max_db, value_db, default_db, on_change, on_gesture_begin: _, on_gesture_end,
+            tint, orientation, length, jump_to_click, glide, readout, entry_gesture,
+        } = self;
+        Fader {
+            min_db, max_db, value_db, default_db, on_change, on_gesture_begin: callback, on_gesture_end,
+            tint, orientation, length, jump_to_click, glide, readout, entry_gesture,
+        }
+    }
+
+    /// Called when the user releases the fader.
+    pub fn on_gesture_end<State, Action>(
+        self,
+        callback: impl Fn(&mut State) -> Action + Send + Sync + 'static,
+    ) -> Fader<F, B, impl Fn(&mut State) -> Action + Send + Sync + 'static> {
+        let Fader {
+            min_db, max_db, value_db, default_db, on_change, on_gesture_begin, on_gesture_end: _,
+            tint, orientation, length, jump_to_click, glide, readout, entry_gesture,
+        } = self;
+        Fader {
+            min_db, max_db, value_db, default_db, on_change, on_gesture_begin, on_gesture_end: callback,
+            tint, orientation, length, jump_to_click, glide, readout, entry_gesture,
+        }
+    }
 }
 
-impl<F> ViewMarker for Fader<F> {}
+impl<F, B, E> ViewMarker for Fader<F, B, E> {}
 
-impl<F, State, Action> View<State, Action, ViewCtx> for Fader<F>
+impl<F, B, E, State, Action> View<State, Action, ViewCtx> for Fader<F, B, E>
 where
     State: 'static,
     Action: 'static,
     F: Fn(&mut State, f64) -> Action + Send + Sync + 'static,
+    B: GestureHandler<State, Action>,
+    E: GestureHandler<State, Action>,
 {
     type Element = Pod<FaderWidget>;
     type ViewState = ();
 
     fn build(&self, ctx: &mut ViewCtx, _: &mut State) -> (Self::Element, Self::ViewState) {
-        let mut w = FaderWidget::new(self.min_db, self.max_db, self.value_db, self.default_db);
+        let mut w = FaderWidget::new(self.min_db, self.max_db, self.value_db, self.default_db)
+            .with_orientation(self.orientation);
+        if let Some(l) = self.length { w = w.with_length(l); }
+        w = w.with_jump_to_click(self.jump_to_click);
+        if let Some(g) = self.glide { w = w.with_glide(g); }
         if let Some(c) = self.tint { w = w.with_tint(c); }
+        if let Some(ref f) = self.readout { w = w.with_readout(f.clone()); }
+        w = w.with_entry_gesture(self.entry_gesture);
         let pod = ctx.with_action_widget(|ctx| ctx.create_pod(w));
         (pod, ())
     }
@@ -72,6 +182,23 @@ where
         if prev.tint != self.tint {
             if let Some(c) = self.tint { FaderWidget::set_tint(&mut element, c); }
         }
+        if prev.orientation != self.orientation {
+            FaderWidget::set_orientation(&mut element, self.orientation);
+        }
+        if prev.length != self.length {
+            if let Some(l) = self.length { FaderWidget::set_length(&mut element, l); }
+        }
+        if prev.jump_to_click != self.jump_to_click {
+            FaderWidget::set_jump_to_click(&mut element, self.jump_to_click);
+        }
+        if prev.glide != self.glide {
+            FaderWidget::set_glide(&mut element, self.glide.unwrap_or(Duration::ZERO));
+        }
+        if prev.entry_gesture != self.entry_gesture {
+            FaderWidget::set_entry_gesture(&mut element, self.entry_gesture);
+        }
+        // Closures can't be compared; the widget only relayouts if the text changes
+        FaderWidget::set_readout(&mut element, self.readout.clone());
     }
 
     fn teardown(&self, _: &mut (), ctx: &mut ViewCtx, element: Mut<'_, Self::Element>) {
@@ -83,8 +210,10 @@ where
         _: Mut<'_, Self::Element>, state: &mut State,
     ) -> MessageResult<Action> {
         if message.take_first().is_some() { return MessageResult::Stale; }
-        match message.take_message::<f64>() {
-            Some(val) => MessageResult::Action((self.on_change)(state, *val)),
+        match message.take_message::<ControlAction<f64>>().map(|a| *a) {
+            Some(ControlAction::Changed(val)) => MessageResult::Action((self.on_change)(state, val)),
+            Some(ControlAction::GestureBegin) => self.on_gesture_begin.call(state),
+            Some(ControlAction::GestureEnd) => self.on_gesture_end.call(state),
             None => MessageResult::Stale,
         }
     }
@@ -0,0 +1,34 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use xilem::core::MessageResult;
+
+/// Callback invoked when the user grabs or releases a control.
+///
+/// Implemented for any `Fn(&mut State) -> Action` closure, and for
+/// [`NoGesture`], the default that ignores gestures.
+pub trait GestureHandler<State, Action>: Send + Sync + 'static {
+    fn call(&self, state: &mut State) -> MessageResult<Action>;
+}
+
+/// Placeholder handler for views without gesture callbacks.
+pub struct NoGesture;
+
+impl<State, Action> GestureHandler<State, Action> for NoGesture {
+    fn call(&self, _: &mut State) -> MessageResult<Action> {
+        MessageResult::Nop
+    }
+}
+
+impl<State, Action, F> GestureHandler<State, Action> for F
+where
+    F: Fn(&mut State) -> Action + Send + Sync + 'static,
+{
+    fn call(&self, state: &mut State) -> MessageResult<Action> {
+        MessageResult::Action(self(state))
+    }
+}
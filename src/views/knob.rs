@@ -5,25 +5,47 @@
 //! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
 //! (compatible with the Xilem licence).
 
+use std::sync::Arc;
+
 use xilem::core::{MessageCtx, Mut, View, ViewMarker};
 use xilem::core::MessageResult;
+use xilem::masonry::core::keyboard::Modifiers;
 use xilem::{Pod, ViewCtx};
 
+use crate::taper::Taper;
+use crate::views::gesture::{GestureHandler, NoGesture};
 use crate::widgets::knob::Knob as KnobWidget;
+use crate::widgets::entry::EntryGesture;
+use crate::widgets::gesture::ControlAction;
+use crate::widgets::readout::ValueFormatter;
 
 /// A rotary knob view for continuous parameter control.
 ///
 /// Drag vertically to adjust the value. The lit arc shows the
-/// distance from the default (reference) value.
-pub struct Knob<F> {
+/// distance from the default (reference) value. When focused, the
+/// arrow, Page, Home/End and Delete keys adjust the value as well.
+///
+/// `on_gesture_begin` / `on_gesture_end` report when the user grabs and
+/// releases the knob, for automation recording.
+pub struct Knob<F, B = NoGesture, E = NoGesture> {
     min: f64,
     max: f64,
     value: f64,
     default: f64,
     on_change: F,
+    on_gesture_begin: B,
+    on_gesture_end: E,
     step: f64,
+    taper: Taper,
+    detent: Option<f64>,
+    detent_width: Option<f64>,
     small: bool,
     tint: Option<xilem::Color>,
+    readout: Option<ValueFormatter>,
+    entry_gesture: EntryGesture,
+    pixels_per_range: Option<f64>,
+    fine_modifier: Option<Modifiers>,
+    fine_divisor: Option<f64>,
 }
 
 /// Create a rotary knob.
@@ -36,35 +58,155 @@ pub fn knob<State, Action>(
     default: f64,
     on_change: impl Fn(&mut State, f64) -> Action + Send + Sync + 'static,
 ) -> Knob<impl Fn(&mut State, f64) -> Action + Send + Sync + 'static> {
-    Knob { min, max, value, default, on_change, step: 0.0, small: false, tint: None }
+    Knob {
+        min, max, value, default, on_change,
+        on_gesture_begin: NoGesture,
+        on_gesture_end: NoGesture,
+        step: 0.0,
+        taper: Taper::Linear,
+        detent: None,
+        detent_width: None,
+        small: false,
+        tint: None,
+        readout: None,
+        entry_gesture: EntryGesture::Disabled,
+        pixels_per_range: None,
+        fine_modifier: None,
+        fine_divisor: None,
+    }
 }
 
-impl<F> Knob<F> {
+impl<F, B, E> Knob<F, B, E> {
     pub fn step(mut self, step: f64) -> Self { self.step = step; self }
     pub fn small(mut self) -> Self { self.small = true; self }
 
+    /// Set how the knob's travel maps onto its range, e.g. `Taper::Log`
+    /// for frequency parameters.
+    pub fn taper(mut self, taper: Taper) -> Self {
+        self.taper = taper;
+        self
+    }
+
+    /// Bipolar mode: the lit arc grows from `value`, which is marked with a
+    /// tick and snaps magnetically while dragging. Typical for pan or detune.
+    pub fn detent(mut self, value: f64) -> Self {
+        self.detent = Some(value);
+        self
+    }
+
+    /// Bipolar mode with the detent at the middle of the range.
+    pub fn bipolar(mut self) -> Self {
+        self.detent = Some((self.min + self.max) / 2.0);
+        self
+    }
+
+    /// Detent capture zone either side, as a fraction of travel. Default 0.03.
+    pub fn detent_width(mut self, width: f64) -> Self {
+        self.detent_width = Some(width);
+        self
+    }
+
     pub fn tint(mut self, color: xilem::Color) -> Self {
         self.tint = Some(color);
         self
     }
+
+    /// Show the formatted value over the knob while hovered or dragged,
+    /// e.g. `.readout(|v| format!("{v:.1} Hz"))`.
+    pub fn readout(mut self, formatter: impl Fn(f64) -> String + Send + Sync + 'static) -> Self {
+        self.readout = Some(Arc::new(formatter));
+        self
+    }
+
+    /// Gesture that opens an inline field for typing an exact value,
+    /// e.g. `EntryGesture::ModifierClick(Modifiers::ALT)`. Values like
+    /// `"440"` or `"1.2k"` are clamped and quantized to the knob's range.
+    pub fn entry_gesture(mut self, gesture: EntryGesture) -> Self {
+        self.entry_gesture = gesture;
+        self
+    }
+
+    /// Vertical drag distance (in pixels) that sweeps the whole range. Default 200.
+    pub fn pixels_per_range(mut self, pixels: f64) -> Self {
+        self.pixels_per_range = Some(pixels);
+        self
+    }
+
+    /// Modifier that enables fine adjustment while dragging or scrolling.
+    /// Default `Modifiers::SHIFT`; pass `Modifiers::empty()` to disable.
+    pub fn fine_modifier(mut self, modifier: Modifiers) -> Self {
+        self.fine_modifier = Some(modifier);
+        self
+    }
+
+    /// How much finer the fine mode is. Default 10.
+    pub fn fine_divisor(mut self, divisor: f64) -> Self {
+        self.fine_divisor = Some(divisor);
+        self
+    }
+
+    /// Called when the user grabs the knob (drag start, or before a
+    /// one-off edit such as a key press, scroll or reset).
+    pub fn on_gesture_begin<State, Action>(
+        self,
+        callback: impl Fn(&mut State) -> Action + Send + Sync + 'static,
+    ) -> Knob<F, impl Fn(&mut State) -> Action + Send + Sync + 'static, E> {
+        let Knob {
+            min, max, value, default, on_change, on_gesture_begin: _, on_gesture_end,
+            step, taper, detent, detent_width, small, tint, readout, entry_gesture,
+            pixels_per_range, fine_modifier, fine_divisor,
+        } = self;
+        Knob {
+            min, max, value, default, on_change, on_gesture_begin: callback, on_gesture_end,
+            step, taper, detent, detent_width, small, tint, readout, entry_gesture,
+            pixels_per_range, fine_modifier, fine_divisor,
+        }
+    }
+
+    /// Called when the user releases the knob.
+    pub fn on_gesture_end<State, Action>(
+        self,
+        callback: impl Fn(&mut State) -> Action + Send + Sync + 'static,
+    ) -> Knob<F, B, impl Fn(&mut State) -> Action + Send + Sync + 'static> {
+        let Knob {
+            min, max, value, default, on_change, on_gesture_begin, on_gesture_end: _,
+            step, taper, detent, detent_width, small, tint, readout, entry_gesture,
+            pixels_per_range, fine_modifier, fine_divisor,
+        } = self;
+        Knob {
+            min, max, value, default, on_change, on_gesture_begin, on_gesture_end: callback,
+            step, taper, detent, detent_width, small, tint, readout, entry_gesture,
+            pixels_per_range, fine_modifier, fine_divisor,
+        }
+    }
 }
 
-impl<F> ViewMarker for Knob<F> {}
+impl<F, B, E> ViewMarker for Knob<F, B, E> {}
 
-impl<F, State, Action> View<State, Action, ViewCtx> for Knob<F>
+impl<F, B, E, State, Action> View<State, Action, ViewCtx> for Knob<F, B, E>
 where
     State: 'static,
     Action: 'static,
     F: Fn(&mut State, f64) -> Action + Send + Sync + 'static,
+    B: GestureHandler<State, Action>,
+    E: GestureHandler<State, Action>,
 {
     type Element = Pod<KnobWidget>;
     type ViewState = ();
 
     fn build(&self, ctx: &mut ViewCtx, _: &mut State) -> (Self::Element, Self::ViewState) {
         let mut w = KnobWidget::new(self.min, self.max, self.value, self.default)
-            .with_small(self.small);
+            .with_small(self.small)
+            .with_taper(self.taper.clone());
         if self.step > 0.0 { w = w.with_step(self.step); }
+        if let Some(d) = self.detent { w = w.with_detent(d); }
+        if let Some(dw) = self.detent_width { w = w.with_detent_width(dw); }
         if let Some(c) = self.tint { w = w.with_tint(c); }
+        if let Some(ref f) = self.readout { w = w.with_readout(f.clone()); }
+        w = w.with_entry_gesture(self.entry_gesture);
+        if let Some(p) = self.pixels_per_range { w = w.with_pixels_per_range(p); }
+        if let Some(m) = self.fine_modifier { w = w.with_fine_modifier(m); }
+        if let Some(d) = self.fine_divisor { w = w.with_fine_divisor(d); }
         let pod = ctx.with_action_widget(|ctx| ctx.create_pod(w));
         (pod, ())
     }
@@ -77,9 +219,28 @@ where
         if prev.min != self.min || prev.max != self.max {
             KnobWidget::set_range(&mut element, self.min, self.max);
         }
+        if prev.detent != self.detent { KnobWidget::set_detent(&mut element, self.detent); }
+        if prev.detent_width != self.detent_width {
+            if let Some(dw) = self.detent_width { KnobWidget::set_detent_width(&mut element, dw); }
+        }
+        if prev.taper != self.taper { KnobWidget::set_taper(&mut element, self.taper.clone()); }
         if prev.tint != self.tint {
             if let Some(c) = self.tint { KnobWidget::set_tint(&mut element, c); }
         }
+        // Closures can't be compared; the widget only relayouts if the text changes
+        KnobWidget::set_readout(&mut element, self.readout.clone());
+        if prev.entry_gesture != self.entry_gesture {
+            KnobWidget::set_entry_gesture(&mut element, self.entry_gesture);
+        }
+        if prev.pixels_per_range != self.pixels_per_range {
+            if let Some(p) = self.pixels_per_range { KnobWidget::set_pixels_per_range(&mut element, p); }
+        }
+        if prev.fine_modifier != self.fine_modifier {
+            if let Some(m) = self.fine_modifier { KnobWidget::set_fine_modifier(&mut element, m); }
+        }
+        if prev.fine_divisor != self.fine_divisor {
+            if let Some(d) = self.fine_divisor { KnobWidget::set_fine_divisor(&mut element, d); }
+        }
     }
 
     fn teardown(&self, _: &mut (), ctx: &mut ViewCtx, element: Mut<'_, Self::Element>) {
@@ -91,8 +252,10 @@ where
         _: Mut<'_, Self::Element>, state: &mut State,
     ) -> MessageResult<Action> {
         if message.take_first().is_some() { return MessageResult::Stale; }
-        match message.take_message::<f64>() {
-            Some(val) => MessageResult::Action((self.on_change)(state, *val)),
+        match message.take_message::<ControlAction<f64>>().map(|a| *a) {
+            Some(ControlAction::Changed(val)) => MessageResult::Action((self.on_change)(state, val)),
+            Some(ControlAction::GestureBegin) => self.on_gesture_begin.call(state),
+            Some(ControlAction::GestureEnd) => self.on_gesture_end.call(state),
             None => MessageResult::Stale,
         }
     }
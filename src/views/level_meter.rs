@@ -5,11 +5,14 @@
 //! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
 //! (compatible with the Xilem licence).
 
+use std::time::Duration;
+
 use xilem::core::{MessageCtx, Mut, View, ViewMarker};
 use xilem::core::MessageResult;
 use xilem::{Pod, ViewCtx};
 
 use crate::widgets::level_meter::{LevelMeter as LevelMeterWidget, MeterScale, MeterStyle, Orientation};
+use crate::widgets::scope::ScopeSource;
 
 /// A power bar / level meter that shows a value in a colored bar.
 ///
@@ -21,7 +24,20 @@ use crate::widgets::level_meter::{LevelMeter as LevelMeterWidget, MeterScale, Me
 /// - `Db` (default): thresholds at -12 dB and 0 dB
 /// - `Linear`: thresholds at 75% and 90%
 ///
-/// Can be horizontal or vertical. Display-only (no interaction).
+/// Can be horizontal or vertical. Display-only, except that a click
+/// resets the clip indicator.
+///
+/// Either pass the level as `value` from app state, or attach a
+/// [`ScopeSource`] with [`LevelMeter::source`] and let the meter follow
+/// the signal on animation frames, like [`scope`](crate::scope), without
+/// rebuilding the view tree. A triple-buffer source shows the peak of
+/// the latest block; a [`ring_buffer`](crate::ring_buffer) source sees
+/// every sample, so no peak is missed.
+///
+/// Ballistics live in the widget and animate on their own:
+/// [`LevelMeter::ballistics`] smooths the bar, [`LevelMeter::peak_hold`]
+/// adds a falling peak marker and [`LevelMeter::clip`] a clip indicator
+/// that stays lit until the meter is clicked.
 pub struct LevelMeter {
     value: f64,
     min: f64,
@@ -29,6 +45,10 @@ pub struct LevelMeter {
     orientation: Orientation,
     style: MeterStyle,
     scale: MeterScale,
+    source: Option<ScopeSource>,
+    ballistics: Option<(Duration, Duration)>,
+    peak_hold: Option<(Duration, f64)>,
+    clip: Option<f64>,
 }
 
 /// Create a horizontal level meter with gradient style, dB scale (default).
@@ -38,6 +58,10 @@ pub fn level_meter(value: f64, min: f64, max: f64) -> LevelMeter {
         orientation: Orientation::Horizontal,
         style: MeterStyle::Gradient,
         scale: MeterScale::Db,
+        source: None,
+        ballistics: None,
+        peak_hold: None,
+        clip: None,
     }
 }
 
@@ -71,6 +95,33 @@ impl LevelMeter {
         self.scale = MeterScale::Linear;
         self
     }
+
+    /// Follow the peak level of a signal instead of `value`.
+    pub fn source(mut self, source: ScopeSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Smooth the bar with time constants for rising (`attack`) and
+    /// falling (`release`) levels, e.g. 10 ms and 300 ms.
+    pub fn ballistics(mut self, attack: Duration, release: Duration) -> Self {
+        self.ballistics = Some((attack, release));
+        self
+    }
+
+    /// Show a peak marker that holds for `hold`, then falls by `fall`
+    /// units per second (dB/s on the dB scale).
+    pub fn peak_hold(mut self, hold: Duration, fall: f64) -> Self {
+        self.peak_hold = Some((hold, fall));
+        self
+    }
+
+    /// Show a clip indicator that latches when the level reaches `level`
+    /// (e.g. 0.0 dBFS). Click the meter to reset it.
+    pub fn clip(mut self, level: f64) -> Self {
+        self.clip = Some(level);
+        self
+    }
 }
 
 impl ViewMarker for LevelMeter {}
@@ -81,18 +132,34 @@ where
     Action: 'static,
 {
     type Element = Pod<LevelMeterWidget>;
-    type ViewState = ();
+    /// Tracks the source ID to detect replacement.
+    type ViewState = u64;
 
     fn build(&self, ctx: &mut ViewCtx, _: &mut State) -> (Self::Element, Self::ViewState) {
-        let w = LevelMeterWidget::new(self.value, self.min, self.max, self.orientation)
+        let mut w = LevelMeterWidget::new(self.value, self.min, self.max, self.orientation)
             .with_style(self.style)
             .with_scale(self.scale);
+        if let Some((attack, release)) = self.ballistics {
+            w = w.with_ballistics(attack, release);
+        }
+        if let Some((hold, fall)) = self.peak_hold {
+            w = w.with_peak_hold(hold, fall);
+        }
+        if let Some(level) = self.clip {
+            w = w.with_clip(level);
+        }
+        let source_id = if let Some(ref src) = self.source {
+            w = w.with_source(src.clone());
+            src.id()
+        } else {
+            0
+        };
         let pod = ctx.with_action_widget(|ctx| ctx.create_pod(w));
-        (pod, ())
+        (pod, source_id)
     }
 
     fn rebuild(
-        &self, prev: &Self, _: &mut (), _: &mut ViewCtx,
+        &self, prev: &Self, view_state: &mut u64, _: &mut ViewCtx,
         mut element: Mut<'_, Self::Element>, _: &mut State,
     ) {
         if prev.value != self.value { LevelMeterWidget::set_value(&mut element, self.value); }
@@ -105,14 +172,31 @@ where
         if prev.scale != self.scale {
             LevelMeterWidget::set_scale(&mut element, self.scale);
         }
+        if prev.ballistics != self.ballistics {
+            let (attack, release) = self.ballistics.unwrap_or_default();
+            LevelMeterWidget::set_ballistics(&mut element, attack, release);
+        }
+        if prev.peak_hold != self.peak_hold {
+            LevelMeterWidget::set_peak_hold(&mut element, self.peak_hold);
+        }
+        if prev.clip != self.clip {
+            LevelMeterWidget::set_clip(&mut element, self.clip);
+        }
+        let source_id = self.source.as_ref().map_or(0, |s| s.id());
+        if source_id != *view_state {
+            if let Some(ref src) = self.source {
+                LevelMeterWidget::set_source(&mut element, src.clone());
+            }
+            *view_state = source_id;
+        }
     }
 
-    fn teardown(&self, _: &mut (), ctx: &mut ViewCtx, element: Mut<'_, Self::Element>) {
+    fn teardown(&self, _: &mut u64, ctx: &mut ViewCtx, element: Mut<'_, Self::Element>) {
         ctx.teardown_action_source(element);
     }
 
     fn message(
-        &self, _: &mut (), _: &mut MessageCtx,
+        &self, _: &mut u64, _: &mut MessageCtx,
         _: Mut<'_, Self::Element>, _: &mut State,
     ) -> MessageResult<Action> {
         MessageResult::Stale
@@ -6,10 +6,14 @@
 //! (compatible with the Xilem licence).
 //!
 pub mod fader;
+pub mod gesture;
 pub mod group_box;
 pub mod knob;
 pub mod param_selector;
 pub mod push_button;
 pub mod scope;
+pub mod slider;
+pub mod spectrogram;
+pub mod spectrum;
 pub mod led;
 pub mod level_meter;
@@ -9,12 +9,18 @@ use xilem::core::{MessageCtx, Mut, View, ViewMarker};
 use xilem::core::MessageResult;
 use xilem::{Pod, ViewCtx};
 
+use crate::views::gesture::{GestureHandler, NoGesture};
+use crate::widgets::gesture::ControlAction;
 use crate::widgets::push_button::PushButton as ButtonWidget;
 
 /// A small circular toggle button view for boolean options.
-pub struct PushButton<F> {
+///
+/// `on_gesture_begin` / `on_gesture_end` fire on press and release.
+pub struct PushButton<F, B = NoGesture, E = NoGesture> {
     active: bool,
     on_toggle: F,
+    on_gesture_begin: B,
+    on_gesture_end: E,
     tint: Option<xilem::Color>,
 }
 
@@ -23,23 +29,48 @@ pub fn push_button<State, Action>(
     active: bool,
     on_toggle: impl Fn(&mut State, bool) -> Action + Send + Sync + 'static,
 ) -> PushButton<impl Fn(&mut State, bool) -> Action + Send + Sync + 'static> {
-    PushButton { active, on_toggle, tint: None }
+    PushButton {
+        active, on_toggle,
+        on_gesture_begin: NoGesture,
+        on_gesture_end: NoGesture,
+        tint: None,
+    }
 }
 
-impl<F> PushButton<F> {
+impl<F, B, E> PushButton<F, B, E> {
     pub fn tint(mut self, color: xilem::Color) -> Self {
         self.tint = Some(color);
         self
     }
+
+    /// Called when the button is pressed.
+    pub fn on_gesture_begin<State, Action>(
+        self,
+        callback: impl Fn(&mut State) -> Action + Send + Sync + 'static,
+    ) -> PushButton<F, impl Fn(&mut State) -> Action + Send + Sync + 'static, E> {
+        let PushButton { active, on_toggle, on_gesture_begin: _, on_gesture_end, tint } = self;
+        PushButton { active, on_toggle, on_gesture_begin: callback, on_gesture_end, tint }
+    }
+
+    /// Called when the button is released.
+    pub fn on_gesture_end<State, Action>(
+        self,
+        callback: impl Fn(&mut State) -> Action + Send + Sync + 'static,
+    ) -> PushButton<F, B, impl Fn(&mut State) -> Action + Send + Sync + 'static> {
+        let PushButton { active, on_toggle, on_gesture_begin, on_gesture_end: _, tint } = self;
+        PushButton { active, on_toggle, on_gesture_begin, on_gesture_end: callback, tint }
+    }
 }
 
-impl<F> ViewMarker for PushButton<F> {}
+impl<F, B, E> ViewMarker for PushButton<F, B, E> {}
 
-impl<F, State, Action> View<State, Action, ViewCtx> for PushButton<F>
+impl<F, B, E, State, Action> View<State, Action, ViewCtx> for PushButton<F, B, E>
 where
     State: 'static,
     Action: 'static,
     F: Fn(&mut State, bool) -> Action + Send + Sync + 'static,
+    B: GestureHandler<State, Action>,
+    E: GestureHandler<State, Action>,
 {
     type Element = Pod<ButtonWidget>;
     type ViewState = ();
@@ -70,8 +101,10 @@ where
         _: Mut<'_, Self::Element>, state: &mut State,
     ) -> MessageResult<Action> {
         if message.take_first().is_some() { return MessageResult::Stale; }
-        match message.take_message::<bool>() {
-            Some(val) => MessageResult::Action((self.on_toggle)(state, *val)),
+        match message.take_message::<ControlAction<bool>>().map(|a| *a) {
+            Some(ControlAction::Changed(val)) => MessageResult::Action((self.on_toggle)(state, val)),
+            Some(ControlAction::GestureBegin) => self.on_gesture_begin.call(state),
+            Some(ControlAction::GestureEnd) => self.on_gesture_end.call(state),
             None => MessageResult::Stale,
         }
     }
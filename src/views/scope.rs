@@ -5,13 +5,17 @@
 //! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
 //! (compatible with the Xilem licence).
 
+use std::time::Duration;
+
 use xilem::core::{MessageCtx, Mut, View, ViewMarker};
 use xilem::core::MessageResult;
 use xilem::{Pod, ViewCtx};
 
-use crate::widgets::scope::Scope as ScopeWidget;
+use crate::widgets::scope::{
+    DisplayMode, Scope as ScopeWidget, TraceLayout, TriggerEdge, TriggerMode,
+};
 
-pub use crate::widgets::scope::{ScopeBuffer, ScopeSource};
+pub use crate::widgets::scope::{SampleReader, ScopeBuffer, ScopeReader, ScopeSource, SourceInUse};
 
 /// An oscilloscope view that displays audio waveforms.
 ///
@@ -23,22 +27,92 @@ pub use crate::widgets::scope::{ScopeBuffer, ScopeSource};
 /// The scope bypasses Xilem's normal view rebuild cycle because audio
 /// data arrives continuously from the DSP thread at audio rate:
 ///
-/// 1. **DSP thread** writes sample buffers into a `triple_buffer::Input`.
+/// 1. **DSP thread** writes sample buffers into a `triple_buffer::Input`,
+///    which hands over the latest block, or pushes every sample into a
+///    [`RingProducer`](crate::RingProducer) for a gapless stream.
 /// 2. A [`ScopeSource`] wraps the corresponding `triple_buffer::Output`
-///    behind an `Arc<Mutex<..>>` so it can be cheaply cloned and shared.
+///    (or, via [`ScopeSource::from_reader`], any [`SampleReader`] such as
+///    a [`RingConsumer`](crate::RingConsumer)) so it can be cheaply
+///    cloned and passed around.
 /// 3. Pass the source to this view: `scope(Some(dsp.scope_source()))`.
-/// 4. On first render the widget calls `request_anim_frame()`. On every
-///    animation frame (~60 fps) it polls the `ScopeSource` for new data,
-///    ingests it (trigger detection + decimation), and requests a repaint.
+/// 4. On first render the widget calls `request_anim_frame()`. On the
+///    first animation frame it takes the source's [`ScopeReader`];
+///    on every frame (~60 fps) it then reads new data straight from the
+///    triple buffer, without locking or copying into a new allocation,
+///    ingests it (trigger detection + min/max envelope), and requests a repaint.
+///    A source feeds one display: a second display on the same source
+///    stays blank (and logs a warning) until the first one lets go.
 /// 5. When the `ScopeSource` is replaced (e.g. audio device change), the
 ///    view detects the new source ID during rebuild and hands it to the
 ///    widget, which restarts the animation loop.
 ///
 /// This means the scope updates at display refresh rate without forcing
 /// Xilem to rebuild the entire view tree on every audio buffer.
+///
+/// # Size
+///
+/// The scope fills the space its parent offers, down to a small minimum,
+/// and draws one display column per pixel. Give it a flex factor to make
+/// it grow, or wrap it in a sized box to pin it.
+///
+/// # Triggering
+///
+/// By default the scope triggers on rising zero-crossings in
+/// [`TriggerMode::Auto`]. Edge, level, holdoff and mode can be set like
+/// on a hardware scope. Dragging the trigger-level marker moves the level
+/// inside the widget; the view only overrides it when the value passed
+/// to [`Scope::trigger_level`] changes.
+///
+/// # Roll mode
+///
+/// [`TriggerMode::Roll`] drops the trigger: new samples enter at the
+/// right edge and the trace scrolls left, for slow signals like LFOs and
+/// envelopes that a trigger cannot lock onto. Pair it with a long
+/// timebase and a [`ring_buffer`](crate::ring_buffer) source, which
+/// delivers every sample, so the trace scrolls without gaps:
+/// `.trigger_mode(TriggerMode::Roll).ms_per_div(500.0)`.
+///
+/// # Timebase
+///
+/// Set [`Scope::sample_rate`] to match the source and [`Scope::ms_per_div`]
+/// to show a fixed time window, e.g. `.sample_rate(44_100.0).ms_per_div(5.0)`.
+/// Divisions are labelled in time relative to the trigger point.
+///
+/// # Multiple channels
+///
+/// A source created with [`ScopeSource::interleaved`] shows one trace per
+/// channel, overlaid or stacked, each in its own color. The trigger
+/// watches [`Scope::trigger_channel`] and all traces share its timing.
+///
+/// # Stereo
+///
+/// With a two-channel source, [`DisplayMode::Xy`] plots left against
+/// right and [`DisplayMode::Goniometer`] shows the same rotated by 45°,
+/// mono pointing straight up. Both show a phase-correlation meter.
+///
+/// # Interaction
+///
+/// Freezing, zoom and measurement cursors live in the widget and need
+/// no view props. Click to freeze or resume; drag or scroll to zoom
+/// (Shift scrolls amplitude) and double-click to reset; right-click to
+/// place two cursors, which read out amplitude, Δt and frequency.
 pub struct Scope {
     source: Option<ScopeSource>,
     wave_color: Option<xilem::Color>,
+    trace_colors: Vec<xilem::Color>,
+    trace_layout: TraceLayout,
+    rms_band: bool,
+    persistence: Option<Duration>,
+    display_mode: DisplayMode,
+    xy_persistence: Option<Duration>,
+    trigger_channel: usize,
+    trigger_edge: TriggerEdge,
+    trigger_mode: TriggerMode,
+    trigger_level: Option<f32>,
+    trigger_hysteresis: Option<f32>,
+    holdoff: Duration,
+    sample_rate: Option<f64>,
+    ms_per_div: Option<f64>,
 }
 
 /// Create an oscilloscope view.
@@ -55,14 +129,118 @@ pub fn scope(source: Option<ScopeSource>) -> Scope {
     Scope {
         source,
         wave_color: None,
+        trace_colors: Vec::new(),
+        trace_layout: TraceLayout::Overlay,
+        rms_band: false,
+        persistence: None,
+        display_mode: DisplayMode::Waveform,
+        xy_persistence: None,
+        trigger_channel: 0,
+        trigger_edge: TriggerEdge::Rising,
+        trigger_mode: TriggerMode::Auto,
+        trigger_level: None,
+        trigger_hysteresis: None,
+        holdoff: Duration::ZERO,
+        sample_rate: None,
+        ms_per_div: None,
     }
 }
 
 impl Scope {
+    /// Color of the first (or only) trace.
     pub fn wave_color(mut self, color: xilem::Color) -> Self {
         self.wave_color = Some(color);
         self
     }
+
+    /// Trace colors by channel, e.g. `[left, right]`. Channels beyond
+    /// the list reuse the colors cyclically.
+    pub fn trace_colors(mut self, colors: impl IntoIterator<Item = xilem::Color>) -> Self {
+        self.trace_colors = colors.into_iter().collect();
+        self
+    }
+
+    /// Overlay all traces (default) or give each its own lane.
+    pub fn trace_layout(mut self, layout: TraceLayout) -> Self {
+        self.trace_layout = layout;
+        self
+    }
+
+    /// Shade the RMS level inside the peak envelope drawn when the
+    /// timebase packs several samples into each pixel.
+    pub fn rms_band(mut self) -> Self {
+        self.rms_band = true;
+        self
+    }
+
+    /// Phosphor persistence: earlier traces fade out over `decay`, and
+    /// paths the signal takes often glow brighter.
+    pub fn persistence(mut self, decay: Duration) -> Self {
+        self.persistence = Some(decay);
+        self
+    }
+
+    /// Waveform (default), XY (Lissajous) or goniometer display.
+    pub fn display_mode(mut self, mode: DisplayMode) -> Self {
+        self.display_mode = mode;
+        self
+    }
+
+    /// How long XY and goniometer traces linger while fading. Default 150 ms.
+    pub fn xy_persistence(mut self, persistence: Duration) -> Self {
+        self.xy_persistence = Some(persistence);
+        self
+    }
+
+    /// Channel the trigger watches. Default 0.
+    pub fn trigger_channel(mut self, channel: usize) -> Self {
+        self.trigger_channel = channel;
+        self
+    }
+
+    pub fn trigger_edge(mut self, edge: TriggerEdge) -> Self {
+        self.trigger_edge = edge;
+        self
+    }
+
+    /// Auto, Normal or Single-shot. In `Single` mode, clicking the
+    /// display re-arms the capture.
+    pub fn trigger_mode(mut self, mode: TriggerMode) -> Self {
+        self.trigger_mode = mode;
+        self
+    }
+
+    /// Signal value the trigger fires at. Default 0.0 (zero-crossing).
+    pub fn trigger_level(mut self, level: f32) -> Self {
+        self.trigger_level = Some(level);
+        self
+    }
+
+    /// Hysteresis band either side of the trigger level. Default 0.02.
+    pub fn trigger_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.trigger_hysteresis = Some(hysteresis);
+        self
+    }
+
+    /// Ignore further triggers for this long after each trigger, e.g. to
+    /// lock onto the start of a repeating pattern.
+    pub fn holdoff(mut self, holdoff: Duration) -> Self {
+        self.holdoff = holdoff;
+        self
+    }
+
+    /// Sample rate of the source data. Default 48 kHz.
+    pub fn sample_rate(mut self, sample_rate: f64) -> Self {
+        self.sample_rate = Some(sample_rate);
+        self
+    }
+
+    /// Timebase: milliseconds per horizontal division (four across the
+    /// display). Without it the scope shows one sample per pixel.
+    pub fn ms_per_div(mut self, ms: f64) -> Self {
+        self.ms_per_div = Some(ms);
+        self
+    }
 }
 
 impl ViewMarker for Scope {}
@@ -77,10 +255,34 @@ where
     type ViewState = u64;
 
     fn build(&self, ctx: &mut ViewCtx, _: &mut State) -> (Self::Element, Self::ViewState) {
-        let mut w = ScopeWidget::new();
+        let mut w = ScopeWidget::new()
+            .with_trace_colors(&self.trace_colors)
+            .with_trace_layout(self.trace_layout)
+            .with_rms_band(self.rms_band)
+            .with_persistence(self.persistence)
+            .with_display_mode(self.display_mode)
+            .with_trigger_channel(self.trigger_channel)
+            .with_trigger_edge(self.trigger_edge)
+            .with_trigger_mode(self.trigger_mode)
+            .with_holdoff(self.holdoff);
         if let Some(c) = self.wave_color {
             w = w.with_wave_color(c);
         }
+        if let Some(l) = self.trigger_level {
+            w = w.with_trigger_level(l);
+        }
+        if let Some(h) = self.trigger_hysteresis {
+            w = w.with_trigger_threshold(h);
+        }
+        if let Some(sr) = self.sample_rate {
+            w = w.with_sample_rate(sr);
+        }
+        if let Some(p) = self.xy_persistence {
+            w = w.with_xy_persistence(p);
+        }
+        if let Some(ms) = self.ms_per_div {
+            w = w.with_ms_per_div(ms);
+        }
         let source_id = if let Some(ref src) = self.source {
             w = w.with_source(src.clone());
             src.id()
@@ -93,12 +295,67 @@ where
 
     fn rebuild(
         &self,
-        _prev: &Self,
+        prev: &Self,
         view_state: &mut Self::ViewState,
         _: &mut ViewCtx,
         mut element: Mut<'_, Self::Element>,
         _: &mut State,
     ) {
+        if prev.trace_colors != self.trace_colors {
+            ScopeWidget::set_trace_colors(&mut element, &self.trace_colors);
+        }
+        if prev.wave_color != self.wave_color {
+            if let Some(c) = self.wave_color {
+                ScopeWidget::set_wave_color(&mut element, c);
+            }
+        }
+        if prev.trace_layout != self.trace_layout {
+            ScopeWidget::set_trace_layout(&mut element, self.trace_layout);
+        }
+        if prev.rms_band != self.rms_band {
+            ScopeWidget::set_rms_band(&mut element, self.rms_band);
+        }
+        if prev.persistence != self.persistence {
+            ScopeWidget::set_persistence(&mut element, self.persistence);
+        }
+        if prev.display_mode != self.display_mode {
+            ScopeWidget::set_display_mode(&mut element, self.display_mode);
+        }
+        if prev.xy_persistence != self.xy_persistence {
+            if let Some(p) = self.xy_persistence {
+                ScopeWidget::set_xy_persistence(&mut element, p);
+            }
+        }
+        if prev.trigger_channel != self.trigger_channel {
+            ScopeWidget::set_trigger_channel(&mut element, self.trigger_channel);
+        }
+        if prev.trigger_edge != self.trigger_edge {
+            ScopeWidget::set_trigger_edge(&mut element, self.trigger_edge);
+        }
+        if prev.trigger_mode != self.trigger_mode {
+            ScopeWidget::set_trigger_mode(&mut element, self.trigger_mode);
+        }
+        if prev.trigger_level != self.trigger_level {
+            if let Some(l) = self.trigger_level {
+                ScopeWidget::set_trigger_level(&mut element, l);
+            }
+        }
+        if prev.trigger_hysteresis != self.trigger_hysteresis {
+            if let Some(h) = self.trigger_hysteresis {
+                ScopeWidget::set_trigger_threshold(&mut element, h);
+            }
+        }
+        if prev.holdoff != self.holdoff {
+            ScopeWidget::set_holdoff(&mut element, self.holdoff);
+        }
+        if prev.sample_rate != self.sample_rate {
+            if let Some(sr) = self.sample_rate {
+                ScopeWidget::set_sample_rate(&mut element, sr);
+            }
+        }
+        if prev.ms_per_div != self.ms_per_div {
+            ScopeWidget::set_ms_per_div(&mut element, self.ms_per_div);
+        }
         let source_id = self.source.as_ref().map_or(0, |s| s.id());
         if source_id != *view_state {
             if let Some(ref src) = self.source {
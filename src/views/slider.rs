@@ -0,0 +1,269 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use std::sync::Arc;
+
+use xilem::core::{MessageCtx, Mut, View, ViewMarker};
+use xilem::masonry::core::keyboard::Modifiers;
+use xilem::core::MessageResult;
+use xilem::{Pod, ViewCtx};
+
+use crate::taper::Taper;
+use crate::views::gesture::{GestureHandler, NoGesture};
+use crate::widgets::entry::EntryGesture;
+use crate::widgets::gesture::ControlAction;
+use crate::widgets::level_meter::Orientation;
+use crate::widgets::readout::ValueFormatter;
+use crate::widgets::slider::{Slider as SliderWidget, SliderTick};
+
+/// A general-purpose slider view with the fader's look.
+///
+/// Emits values in the slider's own units. Supports tapers, steps,
+/// labelled ticks and a bipolar fill. `on_gesture_begin` /
+/// `on_gesture_end` report when the user grabs and releases the slider.
+pub struct Slider<F, B = NoGesture, E = NoGesture> {
+    min: f64,
+    max: f64,
+    value: f64,
+    default: f64,
+    on_change: F,
+    on_gesture_begin: B,
+    on_gesture_end: E,
+    step: f64,
+    taper: Taper,
+    fill_from: Option<f64>,
+    ticks: Vec<SliderTick>,
+    tint: Option<xilem::Color>,
+    orientation: Orientation,
+    length: Option<f64>,
+    readout: Option<ValueFormatter>,
+    entry_gesture: EntryGesture,
+    fine_modifier: Option<Modifiers>,
+    fine_divisor: Option<f64>,
+}
+
+/// Create a slider, vertical by default.
+///
+/// `default` is the value restored on double-click.
+pub fn slider<State, Action>(
+    min: f64,
+    max: f64,
+    value: f64,
+    default: f64,
+    on_change: impl Fn(&mut State, f64) -> Action + Send + Sync + 'static,
+) -> Slider<impl Fn(&mut State, f64) -> Action + Send + Sync + 'static> {
+    Slider {
+        min, max, value, default, on_change,
+        on_gesture_begin: NoGesture,
+        on_gesture_end: NoGesture,
+        step: 0.0,
+        taper: Taper::Linear,
+        fill_from: None,
+        ticks: Vec::new(),
+        tint: None,
+        orientation: Orientation::Vertical,
+        length: None,
+        readout: None,
+        entry_gesture: EntryGesture::Disabled,
+        fine_modifier: None,
+        fine_divisor: None,
+    }
+}
+
+impl<F, B, E> Slider<F, B, E> {
+    pub fn step(mut self, step: f64) -> Self { self.step = step; self }
+
+    pub fn taper(mut self, taper: Taper) -> Self {
+        self.taper = taper;
+        self
+    }
+
+    /// Add an unlabelled tick mark at `value`.
+    pub fn tick(mut self, value: f64) -> Self {
+        self.ticks.push(SliderTick::new(value));
+        self
+    }
+
+    /// Add a tick mark at `value` with a text label beside it.
+    pub fn tick_label(mut self, value: f64, label: impl Into<String>) -> Self {
+        self.ticks.push(SliderTick::labeled(value, label));
+        self
+    }
+
+    /// Bipolar mode: the lit fill grows from `value` towards the grip.
+    pub fn fill_from(mut self, value: f64) -> Self {
+        self.fill_from = Some(value);
+        self
+    }
+
+    /// Bipolar mode with the fill growing from the middle of the range.
+    pub fn bipolar(mut self) -> Self {
+        self.fill_from = Some((self.min + self.max) / 2.0);
+        self
+    }
+
+    pub fn tint(mut self, color: xilem::Color) -> Self {
+        self.tint = Some(color);
+        self
+    }
+
+    /// Lay the track out left to right.
+    pub fn horizontal(mut self) -> Self {
+        self.orientation = Orientation::Horizontal;
+        self
+    }
+
+    /// Preferred track length in pixels. Default 140; the slider shrinks
+    /// when the parent offers less space.
+    pub fn length(mut self, length: f64) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Show the formatted value over the grip while hovered or dragged,
+    /// e.g. `.readout(|v| format!("{v:.0} %"))`.
+    pub fn readout(mut self, formatter: impl Fn(f64) -> String + Send + Sync + 'static) -> Self {
+        self.readout = Some(Arc::new(formatter));
+        self
+    }
+
+    /// Gesture that opens an inline field for typing an exact value.
+    pub fn entry_gesture(mut self, gesture: EntryGesture) -> Self {
+        self.entry_gesture = gesture;
+        self
+    }
+
+    /// Modifier that enables fine scrolling. Default `Modifiers::SHIFT`;
+    /// pass `Modifiers::empty()` to disable.
+    pub fn fine_modifier(mut self, modifier: Modifiers) -> Self {
+        self.fine_modifier = Some(modifier);
+        self
+    }
+
+    /// How much finer the fine mode is. Default 10.
+    pub fn fine_divisor(mut self, divisor: f64) -> Self {
+        self.fine_divisor = Some(divisor);
+        self
+    }
+
+    /// Called when the user grabs the slider (drag start, or before a
+    /// one-off edit such as a key press, scroll or reset).
+    pub fn on_gesture_begin<State, Action>(
+        self,
+        callback: impl Fn(&mut State) -> Action + Send + Sync + 'static,
+    ) -> Slider<F, impl Fn(&mut State) -> Action + Send + Sync + 'static, E> {
+        let Slider {
+            min, max, value, default, on_change, on_gesture_begin: _, on_gesture_end,
+            step, taper, fill_from, ticks, tint, orientation, length, readout,
+            entry_gesture, fine_modifier, fine_divisor,
+        } = self;
+        Slider {
+            min, max, value, default, on_change, on_gesture_begin: callback, on_gesture_end,
+            step, taper, fill_from, ticks, tint, orientation, length, readout,
+            entry_gesture, fine_modifier, fine_divisor,
+        }
+    }
+
+    /// Called when the user releases the slider.
+    pub fn on_gesture_end<State, Action>(
+        self,
+        callback: impl Fn(&mut State) -> Action + Send + Sync + 'static,
+    ) -> Slider<F, B, impl Fn(&mut State) -> Action + Send + Sync + 'static> {
+        let Slider {
+            min, max, value, default, on_change, on_gesture_begin, on_gesture_end: _,
+            step, taper, fill_from, ticks, tint, orientation, length, readout,
+            entry_gesture, fine_modifier, fine_divisor,
+        } = self;
+        Slider {
+            min, max, value, default, on_change, on_gesture_begin, on_gesture_end: callback,
+            step, taper, fill_from, ticks, tint, orientation, length, readout,
+            entry_gesture, fine_modifier, fine_divisor,
+        }
+    }
+}
+
+impl<F, B, E> ViewMarker for Slider<F, B, E> {}
+
+impl<F, B, E, State, Action> View<State, Action, ViewCtx> for Slider<F, B, E>
+where
+    State: 'static,
+    Action: 'static,
+    F: Fn(&mut State, f64) -> Action + Send + Sync + 'static,
+    B: GestureHandler<State, Action>,
+    E: GestureHandler<State, Action>,
+{
+    type Element = Pod<SliderWidget>;
+    type ViewState = ();
+
+    fn build(&self, ctx: &mut ViewCtx, _: &mut State) -> (Self::Element, Self::ViewState) {
+        let mut w = SliderWidget::new(self.min, self.max, self.value, self.default)
+            .with_taper(self.taper.clone())
+            .with_ticks(self.ticks.clone())
+            .with_orientation(self.orientation);
+        if self.step > 0.0 { w = w.with_step(self.step); }
+        if let Some(v) = self.fill_from { w = w.with_fill_from(v); }
+        if let Some(c) = self.tint { w = w.with_tint(c); }
+        if let Some(l) = self.length { w = w.with_length(l); }
+        if let Some(ref f) = self.readout { w = w.with_readout(f.clone()); }
+        w = w.with_entry_gesture(self.entry_gesture);
+        if let Some(m) = self.fine_modifier { w = w.with_fine_modifier(m); }
+        if let Some(d) = self.fine_divisor { w = w.with_fine_divisor(d); }
+        let pod = ctx.with_action_widget(|ctx| ctx.create_pod(w));
+        (pod, ())
+    }
+
+    fn rebuild(
+        &self, prev: &Self, _: &mut (), _: &mut ViewCtx,
+        mut element: Mut<'_, Self::Element>, _: &mut State,
+    ) {
+        if prev.min != self.min || prev.max != self.max {
+            SliderWidget::set_range(&mut element, self.min, self.max);
+        }
+        if prev.value != self.value { SliderWidget::set_value(&mut element, self.value); }
+        if prev.step != self.step { SliderWidget::set_step(&mut element, self.step); }
+        if prev.taper != self.taper { SliderWidget::set_taper(&mut element, self.taper.clone()); }
+        if prev.fill_from != self.fill_from { SliderWidget::set_fill_from(&mut element, self.fill_from); }
+        if prev.ticks != self.ticks { SliderWidget::set_ticks(&mut element, self.ticks.clone()); }
+        if prev.tint != self.tint {
+            if let Some(c) = self.tint { SliderWidget::set_tint(&mut element, c); }
+        }
+        if prev.orientation != self.orientation {
+            SliderWidget::set_orientation(&mut element, self.orientation);
+        }
+        if prev.length != self.length {
+            if let Some(l) = self.length { SliderWidget::set_length(&mut element, l); }
+        }
+        // Closures can't be compared; the widget only relayouts if the text changes
+        SliderWidget::set_readout(&mut element, self.readout.clone());
+        if prev.entry_gesture != self.entry_gesture {
+            SliderWidget::set_entry_gesture(&mut element, self.entry_gesture);
+        }
+        if prev.fine_modifier != self.fine_modifier {
+            if let Some(m) = self.fine_modifier { SliderWidget::set_fine_modifier(&mut element, m); }
+        }
+        if prev.fine_divisor != self.fine_divisor {
+            if let Some(d) = self.fine_divisor { SliderWidget::set_fine_divisor(&mut element, d); }
+        }
+    }
+
+    fn teardown(&self, _: &mut (), ctx: &mut ViewCtx, element: Mut<'_, Self::Element>) {
+        ctx.teardown_action_source(element);
+    }
+
+    fn message(
+        &self, _: &mut (), message: &mut MessageCtx,
+        _: Mut<'_, Self::Element>, state: &mut State,
+    ) -> MessageResult<Action> {
+        if message.take_first().is_some() { return MessageResult::Stale; }
+        match message.take_message::<ControlAction<f64>>().map(|a| *a) {
+            Some(ControlAction::Changed(val)) => MessageResult::Action((self.on_change)(state, val)),
+            Some(ControlAction::GestureBegin) => self.on_gesture_begin.call(state),
+            Some(ControlAction::GestureEnd) => self.on_gesture_end.call(state),
+            None => MessageResult::Stale,
+        }
+    }
+}
@@ -0,0 +1,196 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use xilem::core::{MessageCtx, Mut, View, ViewMarker};
+use xilem::core::MessageResult;
+use xilem::{Pod, ViewCtx};
+
+use crate::fft::{ChannelMode, WindowFunction};
+use crate::widgets::scope::ScopeSource;
+use crate::widgets::spectrogram::{Colormap, Spectrogram as SpectrogramWidget};
+
+/// A scrolling spectrogram view for a [`ScopeSource`].
+///
+/// Polls the source on animation frames like [`scope`](crate::scope)
+/// and [`spectrum`](crate::spectrum), so the waterfall scrolls without
+/// rebuilding the view tree. Time runs right to left, frequency bottom
+/// to top, and level is shown through the [`Colormap`].
+///
+/// The scroll speed is set by the hop: one column per
+/// [`Spectrogram::hop`] samples.
+pub struct Spectrogram {
+    source: Option<ScopeSource>,
+    fft_size: Option<usize>,
+    hop: Option<usize>,
+    window: WindowFunction,
+    channel_mode: ChannelMode,
+    sample_rate: Option<f64>,
+    db_range: Option<(f32, f32)>,
+    colormap: Colormap,
+}
+
+/// Create a spectrogram view.
+///
+/// ```ignore
+/// spectrogram(Some(state.dsp.spectrogram_source()))
+///     .fft_size(2048)
+///     .hop(512)
+///     .colormap(Colormap::Viridis)
+/// ```
+pub fn spectrogram(source: Option<ScopeSource>) -> Spectrogram {
+    Spectrogram {
+        source,
+        fft_size: None,
+        hop: None,
+        window: WindowFunction::Hann,
+        channel_mode: ChannelMode::Mix,
+        sample_rate: None,
+        db_range: None,
+        colormap: Colormap::Magma,
+    }
+}
+
+impl Spectrogram {
+    /// Samples per FFT, rounded to a power of two. Default 1024.
+    pub fn fft_size(mut self, size: usize) -> Self {
+        self.fft_size = Some(size);
+        self
+    }
+
+    /// Samples between columns. Default: a quarter of the FFT size.
+    pub fn hop(mut self, hop: usize) -> Self {
+        self.hop = Some(hop);
+        self
+    }
+
+    pub fn window(mut self, window: WindowFunction) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// How a multi-channel source is analyzed. Default
+    /// [`ChannelMode::Mix`]; [`ChannelMode::Max`] keeps content that is
+    /// out of phase between channels from cancelling.
+    pub fn channel_mode(mut self, mode: ChannelMode) -> Self {
+        self.channel_mode = mode;
+        self
+    }
+
+    /// Sample rate of the source data. Default 48 kHz.
+    pub fn sample_rate(mut self, sample_rate: f64) -> Self {
+        self.sample_rate = Some(sample_rate);
+        self
+    }
+
+    /// Levels spread over the colormap, in dBFS. Default -100 to 0 dB.
+    pub fn db_range(mut self, min: f32, max: f32) -> Self {
+        self.db_range = Some((min, max));
+        self
+    }
+
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+}
+
+impl ViewMarker for Spectrogram {}
+
+impl<State, Action> View<State, Action, ViewCtx> for Spectrogram
+where
+    State: 'static,
+    Action: 'static,
+{
+    type Element = Pod<SpectrogramWidget>;
+    /// Tracks the source ID to detect replacement.
+    type ViewState = u64;
+
+    fn build(&self, ctx: &mut ViewCtx, _: &mut State) -> (Self::Element, Self::ViewState) {
+        let mut w = SpectrogramWidget::new()
+            .with_window(self.window)
+            .with_channel_mode(self.channel_mode)
+            .with_colormap(self.colormap);
+        if let Some(size) = self.fft_size {
+            w = w.with_fft_size(size);
+        }
+        if let Some(hop) = self.hop {
+            w = w.with_hop(hop);
+        }
+        if let Some(sr) = self.sample_rate {
+            w = w.with_sample_rate(sr);
+        }
+        if let Some((min, max)) = self.db_range {
+            w = w.with_db_range(min, max);
+        }
+        let source_id = if let Some(ref src) = self.source {
+            w = w.with_source(src.clone());
+            src.id()
+        } else {
+            0
+        };
+        let pod = ctx.with_action_widget(|ctx| ctx.create_pod(w));
+        (pod, source_id)
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        view_state: &mut Self::ViewState,
+        _: &mut ViewCtx,
+        mut element: Mut<'_, Self::Element>,
+        _: &mut State,
+    ) {
+        if prev.fft_size != self.fft_size {
+            if let Some(size) = self.fft_size {
+                SpectrogramWidget::set_fft_size(&mut element, size);
+            }
+        }
+        if prev.hop != self.hop {
+            SpectrogramWidget::set_hop(&mut element, self.hop);
+        }
+        if prev.window != self.window {
+            SpectrogramWidget::set_window(&mut element, self.window);
+        }
+        if prev.channel_mode != self.channel_mode {
+            SpectrogramWidget::set_channel_mode(&mut element, self.channel_mode);
+        }
+        if prev.sample_rate != self.sample_rate {
+            if let Some(sr) = self.sample_rate {
+                SpectrogramWidget::set_sample_rate(&mut element, sr);
+            }
+        }
+        if prev.db_range != self.db_range {
+            if let Some((min, max)) = self.db_range {
+                SpectrogramWidget::set_db_range(&mut element, min, max);
+            }
+        }
+        if prev.colormap != self.colormap {
+            SpectrogramWidget::set_colormap(&mut element, self.colormap);
+        }
+        let source_id = self.source.as_ref().map_or(0, |s| s.id());
+        if source_id != *view_state {
+            if let Some(ref src) = self.source {
+                SpectrogramWidget::set_source(&mut element, src.clone());
+            }
+            *view_state = source_id;
+        }
+    }
+
+    fn teardown(&self, _: &mut Self::ViewState, ctx: &mut ViewCtx, element: Mut<'_, Self::Element>) {
+        ctx.teardown_action_source(element);
+    }
+
+    fn message(
+        &self,
+        _: &mut Self::ViewState,
+        _message: &mut MessageCtx,
+        _: Mut<'_, Self::Element>,
+        _: &mut State,
+    ) -> MessageResult<Action> {
+        MessageResult::Stale
+    }
+}
@@ -0,0 +1,236 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use std::time::Duration;
+
+use xilem::core::{MessageCtx, Mut, View, ViewMarker};
+use xilem::core::MessageResult;
+use xilem::{Pod, ViewCtx};
+
+use crate::fft::{ChannelMode, WindowFunction};
+use crate::widgets::scope::ScopeSource;
+use crate::widgets::spectrum::Spectrum as SpectrumWidget;
+
+/// A spectrum analyzer view for a [`ScopeSource`].
+///
+/// Uses the same data path as [`scope`](crate::scope): the widget polls
+/// the source on animation frames, so audio updates never rebuild the
+/// view tree. Like the scope, it needs a source of its own; to analyze
+/// the signal a scope shows, publish it to a second source.
+///
+/// # Analysis
+///
+/// Each block of [`Spectrum::fft_size`] samples is windowed (Hann by
+/// default, or Blackman-Harris for lower leakage) and transformed. The
+/// curve is averaged over time by [`Spectrum::smoothing`]; with
+/// [`Spectrum::peak_hold`] a second curve keeps recent maxima.
+pub struct Spectrum {
+    source: Option<ScopeSource>,
+    fft_size: Option<usize>,
+    window: WindowFunction,
+    channel_mode: ChannelMode,
+    smoothing: Option<f32>,
+    peak_hold: Option<Duration>,
+    sample_rate: Option<f64>,
+    freq_range: Option<(f64, f64)>,
+    db_range: Option<(f32, f32)>,
+    color: Option<xilem::Color>,
+}
+
+/// Create a spectrum analyzer view.
+///
+/// ```ignore
+/// spectrum(Some(state.dsp.spectrum_source()))
+///     .fft_size(4096)
+///     .peak_hold(Duration::from_secs(1))
+/// ```
+pub fn spectrum(source: Option<ScopeSource>) -> Spectrum {
+    Spectrum {
+        source,
+        fft_size: None,
+        window: WindowFunction::Hann,
+        channel_mode: ChannelMode::Mix,
+        smoothing: None,
+        peak_hold: None,
+        sample_rate: None,
+        freq_range: None,
+        db_range: None,
+        color: None,
+    }
+}
+
+impl Spectrum {
+    /// Samples per FFT block, rounded to a power of two. Default 2048.
+    pub fn fft_size(mut self, size: usize) -> Self {
+        self.fft_size = Some(size);
+        self
+    }
+
+    pub fn window(mut self, window: WindowFunction) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// How a multi-channel source is analyzed. Default
+    /// [`ChannelMode::Mix`]; [`ChannelMode::Max`] keeps content that is
+    /// out of phase between channels from cancelling.
+    pub fn channel_mode(mut self, mode: ChannelMode) -> Self {
+        self.channel_mode = mode;
+        self
+    }
+
+    /// Averaging between frames, 0.0 (none) to 0.99 (very slow). Default 0.5.
+    pub fn smoothing(mut self, smoothing: f32) -> Self {
+        self.smoothing = Some(smoothing);
+        self
+    }
+
+    /// Show held peaks, kept for `hold` before they fall.
+    pub fn peak_hold(mut self, hold: Duration) -> Self {
+        self.peak_hold = Some(hold);
+        self
+    }
+
+    /// Sample rate of the source data. Default 48 kHz.
+    pub fn sample_rate(mut self, sample_rate: f64) -> Self {
+        self.sample_rate = Some(sample_rate);
+        self
+    }
+
+    /// Displayed frequency range in Hz. Default 20 Hz to 20 kHz. Starts
+    /// at 1 Hz or above and spans at least an octave.
+    pub fn freq_range(mut self, min: f64, max: f64) -> Self {
+        self.freq_range = Some((min, max));
+        self
+    }
+
+    /// Displayed level range in dBFS. Default -90 to 0 dB.
+    pub fn db_range(mut self, min: f32, max: f32) -> Self {
+        self.db_range = Some((min, max));
+        self
+    }
+
+    pub fn color(mut self, color: xilem::Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+impl ViewMarker for Spectrum {}
+
+impl<State, Action> View<State, Action, ViewCtx> for Spectrum
+where
+    State: 'static,
+    Action: 'static,
+{
+    type Element = Pod<SpectrumWidget>;
+    /// Tracks the source ID to detect replacement.
+    type ViewState = u64;
+
+    fn build(&self, ctx: &mut ViewCtx, _: &mut State) -> (Self::Element, Self::ViewState) {
+        let mut w = SpectrumWidget::new()
+            .with_window(self.window)
+            .with_channel_mode(self.channel_mode)
+            .with_peak_hold(self.peak_hold);
+        if let Some(size) = self.fft_size {
+            w = w.with_fft_size(size);
+        }
+        if let Some(s) = self.smoothing {
+            w = w.with_smoothing(s);
+        }
+        if let Some(sr) = self.sample_rate {
+            w = w.with_sample_rate(sr);
+        }
+        if let Some((min, max)) = self.freq_range {
+            w = w.with_freq_range(min, max);
+        }
+        if let Some((min, max)) = self.db_range {
+            w = w.with_db_range(min, max);
+        }
+        if let Some(c) = self.color {
+            w = w.with_color(c);
+        }
+        let source_id = if let Some(ref src) = self.source {
+            w = w.with_source(src.clone());
+            src.id()
+        } else {
+            0
+        };
+        let pod = ctx.with_action_widget(|ctx| ctx.create_pod(w));
+        (pod, source_id)
+    }
+
+    fn rebuild(
+        &self,
+        prev: &Self,
+        view_state: &mut Self::ViewState,
+        _: &mut ViewCtx,
+        mut element: Mut<'_, Self::Element>,
+        _: &mut State,
+    ) {
+        if prev.fft_size != self.fft_size {
+            if let Some(size) = self.fft_size {
+                SpectrumWidget::set_fft_size(&mut element, size);
+            }
+        }
+        if prev.window != self.window {
+            SpectrumWidget::set_window(&mut element, self.window);
+        }
+        if prev.channel_mode != self.channel_mode {
+            SpectrumWidget::set_channel_mode(&mut element, self.channel_mode);
+        }
+        if prev.smoothing != self.smoothing {
+            if let Some(s) = self.smoothing {
+                SpectrumWidget::set_smoothing(&mut element, s);
+            }
+        }
+        if prev.peak_hold != self.peak_hold {
+            SpectrumWidget::set_peak_hold(&mut element, self.peak_hold);
+        }
+        if prev.sample_rate != self.sample_rate {
+            if let Some(sr) = self.sample_rate {
+                SpectrumWidget::set_sample_rate(&mut element, sr);
+            }
+        }
+        if prev.freq_range != self.freq_range {
+            if let Some((min, max)) = self.freq_range {
+                SpectrumWidget::set_freq_range(&mut element, min, max);
+            }
+        }
+        if prev.db_range != self.db_range {
+            if let Some((min, max)) = self.db_range {
+                SpectrumWidget::set_db_range(&mut element, min, max);
+            }
+        }
+        if prev.color != self.color {
+            if let Some(c) = self.color {
+                SpectrumWidget::set_color(&mut element, c);
+            }
+        }
+        let source_id = self.source.as_ref().map_or(0, |s| s.id());
+        if source_id != *view_state {
+            if let Some(ref src) = self.source {
+                SpectrumWidget::set_source(&mut element, src.clone());
+            }
+            *view_state = source_id;
+        }
+    }
+
+    fn teardown(&self, _: &mut Self::ViewState, ctx: &mut ViewCtx, element: Mut<'_, Self::Element>) {
+        ctx.teardown_action_source(element);
+    }
+
+    fn message(
+        &self,
+        _: &mut Self::ViewState,
+        _message: &mut MessageCtx,
+        _: Mut<'_, Self::Element>,
+        _: &mut State,
+    ) -> MessageResult<Action> {
+        MessageResult::Stale
+    }
+}
@@ -0,0 +1,261 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use xilem::masonry::core::keyboard::{Key, KeyState, NamedKey};
+use xilem::masonry::core::{EventCtx, ScrollDelta, TextEvent, UpdateCtx};
+
+use crate::taper::Taper;
+use crate::widgets::entry::{EntryGesture, EntryKey, ValueEntry};
+use crate::widgets::gesture::ControlAction;
+use crate::widgets::readout::ValueReadout;
+
+/// Unstepped key/scroll increment, as a fraction of travel.
+pub(crate) const KEY_FRACTION: f64 = 0.01;
+/// PageUp/PageDown move this many increments by default.
+const PAGE_STEPS: f64 = 10.0;
+/// Trackpad pixels that count as one mouse-wheel notch.
+const SCROLL_PIXELS_PER_NOTCH: f64 = 20.0;
+
+/// Value handling shared by the knob, slider and fader: range, step and taper,
+/// keyboard and scroll increments, inline text entry and the readout.
+///
+/// The widgets keep their own pointer dragging and painting, and commit
+/// through [`commit`](Self::commit) so every change is quantized and
+/// reported the same way.
+pub(crate) struct ControlValue {
+    pub(crate) value: f64,
+    pub(crate) min: f64,
+    pub(crate) max: f64,
+    pub(crate) default: f64,
+    pub(crate) step: f64,
+    pub(crate) taper: Taper,
+    /// Fixed increment per key press or scroll notch when unstepped, in
+    /// value units; `None` moves by a fraction of travel.
+    pub(crate) increment: Option<f64>,
+    /// PageUp/PageDown move this many increments.
+    pub(crate) page_steps: f64,
+    /// Scroll notches not yet applied (trackpads deliver fractions).
+    scroll_accum: f64,
+    /// A pointer drag is under way and still owes its `GestureEnd`.
+    dragging: bool,
+    pub(crate) readout: ValueReadout,
+    pub(crate) entry_gesture: EntryGesture,
+    pub(crate) entry: ValueEntry,
+}
+
+impl ControlValue {
+    pub(crate) fn new(min: f64, max: f64, value: f64, default: f64) -> Self {
+        let (lo, hi) = (min.min(max), min.max(max));
+        Self {
+            value: value.clamp(lo, hi),
+            min,
+            max,
+            default: default.clamp(lo, hi),
+            step: 0.0,
+            taper: Taper::Linear,
+            increment: None,
+            page_steps: PAGE_STEPS,
+            scroll_accum: 0.0,
+            dragging: false,
+            readout: ValueReadout::new(),
+            entry_gesture: EntryGesture::Disabled,
+            entry: ValueEntry::new(),
+        }
+    }
+
+    pub(crate) fn normalized_of(&self, value: f64) -> f64 {
+        self.taper.to_normalized(value, self.min, self.max)
+    }
+
+    pub(crate) fn value_at(&self, norm: f64) -> f64 {
+        self.taper.from_normalized(norm, self.min, self.max)
+    }
+
+    /// Limit a value to the range, which may run from high to low.
+    pub(crate) fn clamp(&self, val: f64) -> f64 {
+        val.clamp(self.min.min(self.max), self.min.max(self.max))
+    }
+
+    fn quantize(&self, val: f64) -> f64 {
+        if self.step > 0.0 {
+            let steps = ((val - self.min) / self.step).round();
+            self.clamp(self.min + steps * self.step)
+        } else {
+            self.clamp(val)
+        }
+    }
+
+    /// Value reached after moving `increments` key/scroll increments:
+    /// whole steps when stepped, otherwise the fixed increment or 1% of
+    /// travel each. Unstepped moves that would jump across `detent` stop
+    /// on it.
+    fn nudged(&self, increments: f64, detent: Option<f64>) -> f64 {
+        if self.step > 0.0 {
+            return self.value + increments * self.step;
+        }
+        let target = match self.increment {
+            Some(increment) => self.value + increments * increment,
+            None => self.value_at(self.normalized_of(self.value) + increments * KEY_FRACTION),
+        };
+        match detent {
+            Some(d) if (self.value - d) * (target - d) < 0.0 => d,
+            _ => target,
+        }
+    }
+
+    /// Quantize `val`, but keep a detent exactly: it needn't sit on the
+    /// step grid.
+    fn settle(&self, val: f64, detent: Option<f64>) -> f64 {
+        if detent == Some(val) { self.clamp(val) } else { self.quantize(val) }
+    }
+
+    /// Quantize and store a new value, emitting an action if it changed.
+    pub(crate) fn commit(&mut self, ctx: &mut EventCtx<'_>, val: f64) {
+        let new_val = self.quantize(val);
+        self.store(ctx, new_val);
+    }
+
+    /// Store a value as is (only clamped), such as a detent off the step
+    /// grid, emitting an action if it changed.
+    pub(crate) fn commit_exact(&mut self, ctx: &mut EventCtx<'_>, val: f64) {
+        let new_val = self.clamp(val);
+        self.store(ctx, new_val);
+    }
+
+    fn store(&mut self, ctx: &mut EventCtx<'_>, new_val: f64) {
+        if (self.value - new_val).abs() > f64::EPSILON {
+            self.value = new_val;
+            ctx.submit_action::<ControlAction<f64>>(ControlAction::Changed(self.value));
+            ctx.request_render();
+            if self.readout.invalidate() { ctx.request_layout(); }
+        }
+    }
+
+    /// Capture the pointer and open a drag gesture.
+    pub(crate) fn begin_drag(&mut self, ctx: &mut EventCtx<'_>) {
+        ctx.capture_pointer();
+        ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureBegin);
+        self.dragging = true;
+    }
+
+    /// Close the drag gesture, if one is open, and release the pointer.
+    /// Returns true if a drag was open.
+    pub(crate) fn end_drag(&mut self, ctx: &mut EventCtx<'_>) -> bool {
+        if !self.dragging {
+            return false;
+        }
+        self.dragging = false;
+        ctx.release_pointer();
+        ctx.request_render();
+        ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureEnd);
+        true
+    }
+
+    /// Close the drag gesture when the widget is disabled or stashed
+    /// mid-drag; the pointer capture goes with it. Returns true if a
+    /// drag was open.
+    pub(crate) fn cancel_drag(&mut self, ctx: &mut UpdateCtx<'_>) -> bool {
+        if !self.dragging {
+            return false;
+        }
+        self.dragging = false;
+        ctx.request_render();
+        ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureEnd);
+        true
+    }
+
+    /// Apply a one-off edit (key, scroll, text entry) as a complete gesture.
+    /// Returns true if the value moved.
+    fn commit_edit(&mut self, ctx: &mut EventCtx<'_>, val: f64, detent: Option<f64>) -> bool {
+        let new_val = self.settle(val, detent);
+        if (new_val - self.value).abs() <= f64::EPSILON {
+            return false;
+        }
+        ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureBegin);
+        self.store(ctx, new_val);
+        ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureEnd);
+        true
+    }
+
+    /// Move by one increment per scroll notch. `fine_divisor` is set while
+    /// the fine modifier is held and only applies to unstepped controls.
+    ///
+    /// Vertical scrolling is used, or horizontal scrolling when there is
+    /// none (tilt wheels, sideways swipes, and Shift+wheel on platforms
+    /// that turn it sideways). The event is only marked handled when the
+    /// value moves, so at the ends of the range it can scroll a parent.
+    pub(crate) fn on_scroll(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        delta: &ScrollDelta,
+        fine_divisor: Option<f64>,
+        detent: Option<f64>,
+    ) {
+        let either = |x: f64, y: f64| if y != 0.0 { y } else { x };
+        self.scroll_accum += match delta {
+            ScrollDelta::LineDelta(x, y) => either(*x as f64, *y as f64),
+            ScrollDelta::PixelDelta(p) => either(p.x, p.y) / SCROLL_PIXELS_PER_NOTCH,
+            ScrollDelta::PageDelta(x, y) => either(*x as f64, *y as f64) * self.page_steps,
+        };
+        // Stepped controls wait for a whole notch so slow trackpad
+        // scrolling doesn't get quantized away.
+        let notches = if self.step > 0.0 { self.scroll_accum.trunc() } else { self.scroll_accum };
+        if notches == 0.0 { return; }
+        self.scroll_accum -= notches;
+        let increments = match fine_divisor {
+            Some(divisor) if self.step <= 0.0 => notches / divisor,
+            _ => notches,
+        };
+        if self.commit_edit(ctx, self.nudged(increments, detent), detent) {
+            ctx.set_handled();
+        }
+    }
+
+    /// Keyboard handling while focused: typing into the open entry field,
+    /// Enter to open it, and the navigation keys.
+    pub(crate) fn on_text_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        event: &TextEvent,
+        detent: Option<f64>,
+    ) {
+        let TextEvent::Keyboard(key_event) = event else { return; };
+        if key_event.state != KeyState::Down { return; }
+        if self.entry.is_open() {
+            match self.entry.handle_key(&key_event.key, key_event.modifiers) {
+                EntryKey::Ignored => return,
+                EntryKey::Edited | EntryKey::Cancel | EntryKey::Commit(None) => {}
+                EntryKey::Commit(Some(v)) => {
+                    self.commit_edit(ctx, v, detent);
+                }
+            }
+            ctx.set_handled();
+            ctx.request_layout();
+            ctx.request_render();
+            return;
+        }
+        if key_event.key == Key::Named(NamedKey::Enter) && self.entry_gesture != EntryGesture::Disabled {
+            ctx.set_handled();
+            self.entry.open();
+            ctx.request_layout();
+            ctx.request_render();
+            return;
+        }
+        let target = match &key_event.key {
+            Key::Named(NamedKey::ArrowUp | NamedKey::ArrowRight) => self.nudged(1.0, detent),
+            Key::Named(NamedKey::ArrowDown | NamedKey::ArrowLeft) => self.nudged(-1.0, detent),
+            Key::Named(NamedKey::PageUp) => self.nudged(self.page_steps, detent),
+            Key::Named(NamedKey::PageDown) => self.nudged(-self.page_steps, detent),
+            Key::Named(NamedKey::Home) => self.min,
+            Key::Named(NamedKey::End) => self.max,
+            Key::Named(NamedKey::Delete | NamedKey::Backspace) => self.default,
+            _ => return,
+        };
+        ctx.set_handled();
+        self.commit_edit(ctx, target, detent);
+    }
+}
@@ -0,0 +1,183 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use xilem::masonry::core::BrushIndex;
+use xilem::masonry::core::keyboard::{Key, Modifiers, NamedKey};
+use xilem::masonry::imaging::Painter;
+use xilem::masonry::kurbo::{Line, Point, Stroke};
+use xilem::masonry::parley::{FontContext, Layout, LayoutContext};
+use xilem::masonry::peniko::Color;
+
+use crate::widgets::readout::{layout_text, paint_badge};
+
+const MIN_FIELD_WIDTH: f64 = 36.0;
+
+/// Gesture that opens inline text entry on a continuous control.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum EntryGesture {
+    /// No text entry. Double-click resets to the default value.
+    #[default]
+    Disabled,
+    /// Double-click opens text entry instead of resetting.
+    DoubleClick,
+    /// Clicking with these modifiers held opens text entry.
+    /// Double-click keeps resetting to the default value.
+    ModifierClick(Modifiers),
+}
+
+impl EntryGesture {
+    /// Whether a pointer press with this click count and modifiers opens entry.
+    pub(crate) fn opens_on(self, click_count: u8, modifiers: Modifiers) -> bool {
+        match self {
+            EntryGesture::Disabled => false,
+            EntryGesture::DoubleClick => click_count == 2,
+            EntryGesture::ModifierClick(m) => !m.is_empty() && modifiers.contains(m),
+        }
+    }
+}
+
+/// Outcome of a key press while the entry field is open.
+pub(crate) enum EntryKey {
+    /// The key doesn't concern the field.
+    Ignored,
+    /// The text changed; relayout and repaint.
+    Edited,
+    /// Enter was pressed; the field closed with this parsed value.
+    Commit(Option<f64>),
+    /// Escape was pressed; the field closed without a value.
+    Cancel,
+}
+
+/// An inline text field for typing an exact value over a control.
+///
+/// Like [`ValueReadout`](crate::widgets::readout::ValueReadout), the text
+/// layout is rebuilt in the owning widget's `layout` pass.
+pub(crate) struct ValueEntry {
+    /// The typed text; `Some` while the field is open.
+    text: Option<String>,
+    layout: Option<Layout<BrushIndex>>,
+    dirty: bool,
+}
+
+impl ValueEntry {
+    pub(crate) fn new() -> Self {
+        Self { text: None, layout: None, dirty: false }
+    }
+
+    pub(crate) fn is_open(&self) -> bool {
+        self.text.is_some()
+    }
+
+    pub(crate) fn open(&mut self) {
+        self.text = Some(String::new());
+        self.dirty = true;
+    }
+
+    pub(crate) fn close(&mut self) {
+        self.text = None;
+        self.layout = None;
+    }
+
+    /// Apply a key press. Characters typed with Ctrl or Cmd held are
+    /// shortcuts, not text, and are ignored so they can reach the app.
+    pub(crate) fn handle_key(&mut self, key: &Key, modifiers: Modifiers) -> EntryKey {
+        let Some(ref mut text) = self.text else { return EntryKey::Ignored; };
+        match key {
+            Key::Named(NamedKey::Enter) => {
+                let value = parse_value(text);
+                self.close();
+                EntryKey::Commit(value)
+            }
+            Key::Named(NamedKey::Escape) => {
+                self.close();
+                EntryKey::Cancel
+            }
+            Key::Named(NamedKey::Backspace) => {
+                text.pop();
+                self.dirty = true;
+                EntryKey::Edited
+            }
+            // AltGr arrives as Ctrl+Alt on Windows and does type text
+            Key::Character(_) if (modifiers.ctrl() && !modifiers.alt()) || modifiers.meta() => {
+                EntryKey::Ignored
+            }
+            Key::Character(c) if !c.chars().any(char::is_control) => {
+                text.push_str(c);
+                self.dirty = true;
+                EntryKey::Edited
+            }
+            _ => EntryKey::Ignored,
+        }
+    }
+
+    /// Rebuild the text layout if the typed text changed.
+    pub(crate) fn rebuild(&mut self, contexts: (&mut FontContext, &mut LayoutContext<BrushIndex>)) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        if let Some(ref text) = self.text {
+            self.layout = Some(layout_text(contexts, text));
+        }
+    }
+
+    /// Paint the field with a caret, centered on `center`.
+    pub(crate) fn paint(&self, painter: &mut Painter<'_>, center: Point, tint: Color) {
+        let Some(ref layout) = self.layout else { return; };
+        let text_rect = paint_badge(painter, layout, center, MIN_FIELD_WIDTH);
+        let caret_x = text_rect.x1 + 1.0;
+        painter.stroke(
+            Line::new(Point::new(caret_x, text_rect.y0), Point::new(caret_x, text_rect.y1)),
+            &Stroke::new(1.0),
+            tint,
+        ).draw();
+    }
+}
+
+/// Units that may follow a typed number, compared case-insensitively.
+const UNITS: [&str; 4] = ["hz", "db", "s", "%"];
+
+/// Parse a typed value such as `"440"`, `"-6 dB"`, `"1.2k"`, `"2.5 kHz"`,
+/// `"20ms"` or `"-inf"`.
+///
+/// The number may be followed by an SI prefix (`k`, `M` or `m`) and a
+/// unit (Hz, dB, s or %), each optional. The unit only labels the
+/// number; any other trailing text makes the value invalid. Returns
+/// `None` if the text isn't a value.
+pub(crate) fn parse_value(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let lower = text.to_ascii_lowercase();
+    let infinities = [("-inf", f64::NEG_INFINITY), ("+inf", f64::INFINITY), ("inf", f64::INFINITY)];
+    for (word, value) in infinities {
+        if let Some(rest) = lower.strip_prefix(word) {
+            return is_unit(rest.trim_start()).then_some(value);
+        }
+    }
+    let number_len = text
+        .char_indices()
+        .find(|&(i, c)| !(c.is_ascii_digit() || c == '.' || (i == 0 && (c == '-' || c == '+'))))
+        .map_or(text.len(), |(i, _)| i);
+    let value: f64 = text[..number_len].parse().ok()?;
+    let suffix = text[number_len..].trim_start();
+    if is_unit(suffix) {
+        return Some(value);
+    }
+    // Only a prefix on its own or before a unit, so "Mix" or "mute" are rejected
+    let mut chars = suffix.chars();
+    let multiplier = match chars.next()? {
+        'k' | 'K' => 1e3,
+        'M' => 1e6,
+        'm' => 1e-3,
+        _ => return None,
+    };
+    is_unit(chars.as_str()).then_some(value * multiplier)
+}
+
+/// Whether `text` is empty or one of the known [`UNITS`].
+fn is_unit(text: &str) -> bool {
+    text.is_empty() || UNITS.iter().any(|unit| text.eq_ignore_ascii_case(unit))
+}
@@ -5,121 +5,262 @@
 //! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
 //! (compatible with the Xilem licence).
 
+use std::time::Duration;
+
 use xilem::masonry::accesskit::{Node, Role};
 use xilem::masonry::core::{
     AccessCtx, EventCtx, LayoutCtx, MeasureCtx, PaintCtx, PointerButtonEvent, PointerEvent,
-    PointerUpdate, PropertiesMut, PropertiesRef, RegisterCtx, Update, UpdateCtx, Widget, WidgetId,
-    WidgetMut,
+    PointerScrollEvent, PointerUpdate, PropertiesMut, PropertiesRef, RegisterCtx, TextEvent,
+    Update, UpdateCtx, Widget, WidgetId, WidgetMut,
 };
 use xilem::masonry::imaging::Painter;
-use xilem::masonry::kurbo::{
-    Axis, Cap, Line, Point, Rect, RoundedRect, Size, Stroke,
-};
+use xilem::masonry::kurbo::{Axis, Point, Size, Stroke};
 use xilem::masonry::layout::LenReq;
-use xilem::masonry::peniko::{Color, Fill};
+use xilem::masonry::peniko::Color;
 
 use smallvec::SmallVec;
 use tracing::trace_span;
 
 use crate::theme::DEFAULT_TINT;
-
-const FADER_WIDTH: f64 = 32.0;
-const FADER_HEIGHT: f64 = 140.0;
-const TRACK_WIDTH: f64 = 4.0;
-const GRIP_WIDTH: f64 = 24.0;
-const GRIP_HEIGHT: f64 = 14.0;
-const GRIP_RADIUS: f64 = 3.0;
-const TRACK_MARGIN: f64 = GRIP_HEIGHT / 2.0 + 4.0;
-
-/// A vertical fader (slider) with a grip knob and logarithmic scale.
+use crate::widgets::control::ControlValue;
+use crate::widgets::entry::EntryGesture;
+use crate::widgets::gesture::ControlAction;
+use crate::widgets::level_meter::Orientation;
+use crate::widgets::readout::ValueFormatter;
+use crate::widgets::track::{self, Track, DEFAULT_LENGTH, MIN_LENGTH, THICKNESS, TRACK_WIDTH};
+
+/// Arrow-key increment in dB.
+const KEY_STEP_DB: f64 = 0.5;
+/// PageUp/PageDown increment in dB.
+const PAGE_STEP_DB: f64 = 6.0;
+/// Scroll increment per wheel notch in dB while Shift is held.
+const FINE_STEP_DB: f64 = 0.1;
+/// Pointer travel (in pixels) a drag needs before it cancels a glide.
+const DRAG_SLOP: f64 = 3.0;
+
+/// A fader (slider) with a grip knob and logarithmic scale.
 ///
 /// Designed for volume control. The logarithmic mapping means small
 /// movements at the top produce fine dB adjustments while the bottom
 /// range covers the full attenuation sweep.
+///
+/// Vertical by default; horizontal faders (crossfaders, pan) increase
+/// from left to right. The track takes the space offered by the parent,
+/// up to the preferred length set with [`Self::with_length`].
+///
+/// When focused, arrow keys move the fader by 0.5 dB, PageUp/PageDown
+/// by 6 dB, Home/End jump to min/max, and Delete/Backspace reset to
+/// the default level. Each scroll-wheel notch moves it by 0.5 dB, or by
+/// 0.1 dB with Shift held.
+///
+/// With a readout formatter set, the formatted dB value is shown over the
+/// grip while the fader is hovered or dragged.
+///
+/// An [`EntryGesture`] can open an inline text field for typing an exact
+/// dB value such as `-6 dB` (Enter also opens it while focused).
+///
+/// In jump-to-click mode, clicking the track away from the grip moves the
+/// grip to the pointer, either at once or gliding there over the glide
+/// time; keep the button held to carry on dragging from there. Clicking
+/// the grip itself always starts a relative drag.
+///
+/// Emits [`ControlAction`]s: dB changes bracketed by gesture begin/end.
+/// A glide's changes belong to the click that started it, so the gesture
+/// ends once both the glide and the click are over.
 pub struct Fader {
-    value: f64,
-    min_db: f64,
-    max_db: f64,
-    default_db: f64,
+    /// Value in dB, moved in fixed dB increments by keys and scrolling
+    control: ControlValue,
     tint: Color,
-    drag_start_y: Option<f64>,
+    orientation: Orientation,
+    length: f64,
+    drag_start: Option<Point>,
     drag_start_value: f64,
+    jump_to_click: bool,
+    glide_time: Duration,
+    glide: Option<Glide>,
+    /// The pointer was released mid-glide; end the gesture when it lands.
+    end_after_glide: bool,
+    /// A click or drag gesture is open and still owes its `GestureEnd`.
+    in_gesture: bool,
+}
+
+/// An animated move of the grip towards a clicked position.
+struct Glide {
+    from: f64,
+    to: f64,
+    elapsed: Duration,
 }
 
 impl Fader {
     pub fn new(min_db: f64, max_db: f64, value_db: f64, default_db: f64) -> Self {
-        let norm = Self::db_to_normalized(value_db, min_db, max_db);
+        let mut control = ControlValue::new(min_db, max_db, value_db, default_db);
+        control.increment = Some(KEY_STEP_DB);
+        control.page_steps = PAGE_STEP_DB / KEY_STEP_DB;
         Self {
-            value: norm,
-            min_db,
-            max_db,
-            default_db: default_db.clamp(min_db, max_db),
+            control,
             tint: DEFAULT_TINT,
-            drag_start_y: None,
+            orientation: Orientation::Vertical,
+            length: DEFAULT_LENGTH,
+            drag_start: None,
             drag_start_value: 0.0,
+            jump_to_click: false,
+            glide_time: Duration::ZERO,
+            glide: None,
+            end_after_glide: false,
+            in_gesture: false,
         }
     }
 
     pub fn set_value_db(this: &mut WidgetMut<'_, Self>, value_db: f64) {
-        let norm = Self::db_to_normalized(value_db, this.widget.min_db, this.widget.max_db);
-        if (this.widget.value - norm).abs() > f64::EPSILON {
-            this.widget.value = norm;
+        let control = &mut this.widget.control;
+        let db = control.clamp(value_db);
+        if (control.value - db).abs() > f64::EPSILON {
+            control.value = db;
             this.ctx.request_render();
+            if control.readout.invalidate() {
+                this.ctx.request_layout();
+            }
         }
     }
 
     pub fn set_range(this: &mut WidgetMut<'_, Self>, min_db: f64, max_db: f64) {
-        this.widget.min_db = min_db;
-        this.widget.max_db = max_db;
+        let control = &mut this.widget.control;
+        control.min = min_db;
+        control.max = max_db;
+        control.value = control.clamp(control.value);
+        control.default = control.clamp(control.default);
         this.ctx.request_render();
+        if control.readout.invalidate() {
+            this.ctx.request_layout();
+        }
     }
 
     pub fn with_tint(mut self, color: Color) -> Self { self.tint = color; self }
 
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set the preferred track length in pixels. Default 140.
+    pub fn with_length(mut self, length: f64) -> Self {
+        self.length = length.max(MIN_LENGTH);
+        self
+    }
+
+    /// Clicking the track (not the grip) moves the grip to the pointer.
+    pub fn with_jump_to_click(mut self, jump: bool) -> Self {
+        self.jump_to_click = jump;
+        self
+    }
+
+    /// In jump-to-click mode, animate the jump over `time` instead of
+    /// moving at once. Zero (the default) disables the glide.
+    pub fn with_glide(mut self, time: Duration) -> Self {
+        self.glide_time = time;
+        self
+    }
+
+    pub fn set_jump_to_click(this: &mut WidgetMut<'_, Self>, jump: bool) {
+        this.widget.jump_to_click = jump;
+    }
+
+    pub fn set_glide(this: &mut WidgetMut<'_, Self>, time: Duration) {
+        this.widget.glide_time = time;
+    }
+
+    pub fn set_orientation(this: &mut WidgetMut<'_, Self>, orientation: Orientation) {
+        this.widget.orientation = orientation;
+        this.ctx.request_layout();
+    }
+
+    pub fn set_length(this: &mut WidgetMut<'_, Self>, length: f64) {
+        this.widget.length = length.max(MIN_LENGTH);
+        this.ctx.request_layout();
+    }
+
+    /// Show the dB value, formatted by `formatter`, while hovered or dragged.
+    pub fn with_readout(mut self, formatter: ValueFormatter) -> Self {
+        let value = self.control.value;
+        self.control.readout.set_formatter(Some(formatter), value);
+        self
+    }
+
+    /// Set the gesture that opens inline text entry.
+    pub fn with_entry_gesture(mut self, gesture: EntryGesture) -> Self {
+        self.control.entry_gesture = gesture;
+        self
+    }
+
+    pub fn set_entry_gesture(this: &mut WidgetMut<'_, Self>, gesture: EntryGesture) {
+        this.widget.control.entry_gesture = gesture;
+        if gesture == EntryGesture::Disabled && this.widget.control.entry.is_open() {
+            this.widget.control.entry.close();
+            this.ctx.request_render();
+        }
+    }
+
+    pub fn set_readout(this: &mut WidgetMut<'_, Self>, formatter: Option<ValueFormatter>) {
+        let value = this.widget.control.value;
+        if this.widget.control.readout.set_formatter(formatter, value) {
+            this.ctx.request_layout();
+        }
+    }
+
     pub fn set_tint(this: &mut WidgetMut<'_, Self>, color: Color) {
         this.widget.tint = color;
         this.ctx.request_render();
     }
 
-    /// Convert dB to normalized 0..1 using linear dB scaling.
+    /// Fader position (0..1) of a dB value.
     /// Professional faders use linear dB: fader position is proportional to dB.
     /// Since dB = 20*log10(amplitude), this already provides the correct
     /// logarithmic taper for perceptually uniform volume control.
-    fn db_to_normalized(db: f64, min_db: f64, max_db: f64) -> f64 {
-        let range = max_db - min_db;
-        if range.abs() < f64::EPSILON {
-            return 0.0;
-        }
-        ((db - min_db) / range).clamp(0.0, 1.0)
+    fn normalized_of(&self, db: f64) -> f64 {
+        self.control.normalized_of(db)
     }
 
-    /// Convert normalized 0..1 back to dB.
-    fn normalized_to_db(norm: f64, min_db: f64, max_db: f64) -> f64 {
-        min_db + norm * (max_db - min_db)
+    /// Current fader position (0..1).
+    fn normalized(&self) -> f64 {
+        self.normalized_of(self.control.value)
     }
 
-    fn current_db(&self) -> f64 {
-        Self::normalized_to_db(self.value, self.min_db, self.max_db)
+    /// Move to a fader position, emitting the dB value if it changed.
+    fn commit_normalized(&mut self, ctx: &mut EventCtx<'_>, norm: f64) {
+        self.control.commit(ctx, self.control.value_at(norm));
     }
 
-    fn track_range(height: f64) -> (f64, f64) {
-        (TRACK_MARGIN, height - TRACK_MARGIN)
+    fn track(&self, size: Size) -> Track {
+        Track::new(self.orientation, size, 0.0)
     }
 
-    fn grip_y(&self, height: f64) -> f64 {
-        let (top, bottom) = Self::track_range(height);
-        bottom - self.value * (bottom - top)
+    /// Abandon a running glide where it is, closing its gesture if the
+    /// click that started it is already over.
+    fn stop_glide(&mut self, ctx: &mut EventCtx<'_>) {
+        self.glide = None;
+        if self.end_after_glide {
+            self.end_after_glide = false;
+            self.in_gesture = false;
+            ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureEnd);
+        }
     }
 
-    #[allow(dead_code)]
-    fn y_to_normalized(y: f64, height: f64) -> f64 {
-        let (top, bottom) = Self::track_range(height);
-        ((bottom - y) / (bottom - top)).clamp(0.0, 1.0)
+    /// Stop dragging and gliding, closing the gesture if one is open.
+    fn end_gesture(&mut self, ctx: &mut EventCtx<'_>) {
+        self.drag_start = None;
+        self.glide = None;
+        self.end_after_glide = false;
+        if self.in_gesture {
+            self.in_gesture = false;
+            ctx.release_pointer();
+            ctx.request_render();
+            ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureEnd);
+        }
     }
 }
 
 impl Widget for Fader {
-    type Action = f64;
+    type Action = ControlAction<f64>;
 
     fn on_pointer_event(
         &mut self,
@@ -127,53 +268,142 @@ impl Widget for Fader {
         _props: &mut PropertiesMut<'_>,
         event: &PointerEvent,
     ) {
+        // Handled even when disabled, so a drag always ends
+        match event {
+            PointerEvent::Up(..) if self.drag_start.is_some() => {
+                if self.glide.is_some() && !ctx.is_disabled() {
+                    // The glide carries on; its gesture ends when it lands
+                    ctx.release_pointer();
+                    ctx.request_render();
+                    self.drag_start = None;
+                    self.end_after_glide = true;
+                } else {
+                    self.end_gesture(ctx);
+                }
+                return;
+            }
+            PointerEvent::Cancel(..) => {
+                self.end_gesture(ctx);
+                return;
+            }
+            _ => {}
+        }
         if ctx.is_disabled() {
             return;
         }
         match event {
             PointerEvent::Down(PointerButtonEvent { state, .. }) => {
                 ctx.request_focus();
+                self.stop_glide(ctx);
+                if self.control.entry.is_open() {
+                    // Clicking the control again abandons the typed text
+                    self.control.entry.close();
+                    ctx.request_render();
+                    return;
+                }
+                if self.control.entry_gesture.opens_on(state.count, state.modifiers) {
+                    self.control.entry.open();
+                    ctx.request_layout();
+                    ctx.request_render();
+                    return;
+                }
                 if state.count == 2 {
                     // Double-click: reset to default
-                    self.value = Self::db_to_normalized(self.default_db, self.min_db, self.max_db);
-                    ctx.submit_action::<f64>(self.default_db);
+                    self.control.value = self.control.default;
+                    ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureBegin);
+                    ctx.submit_action::<ControlAction<f64>>(ControlAction::Changed(self.control.value));
+                    ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureEnd);
                     ctx.request_render();
+                    if self.control.readout.invalidate() {
+                        ctx.request_layout();
+                    }
                     return;
                 }
                 ctx.capture_pointer();
+                ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureBegin);
+                self.in_gesture = true;
                 let pos = ctx.local_position(state.position);
-                self.drag_start_y = Some(pos.y);
-                self.drag_start_value = self.value;
-            }
-            PointerEvent::Move(PointerUpdate { current, .. }) => {
-                if ctx.is_active() {
-                    if let Some(start_y) = self.drag_start_y {
-                        let pos = ctx.local_position(current.position);
-                        let height = ctx.content_box_size().height;
-                        let (top, bottom) = Self::track_range(height);
-                        let dy = start_y - pos.y;
-                        let range = bottom - top;
-                        let delta = dy / range;
-                        let new_val = (self.drag_start_value + delta).clamp(0.0, 1.0);
-                        if (self.value - new_val).abs() > f64::EPSILON {
-                            self.value = new_val;
-                            let db = self.current_db();
-                            ctx.submit_action::<f64>(db);
-                            ctx.request_render();
-                        }
+                self.drag_start = Some(pos);
+                self.drag_start_value = self.normalized();
+                let track = self.track(ctx.content_box_size());
+                if self.jump_to_click && !track.grip_contains(self.drag_start_value, pos) {
+                    // Further dragging continues from the clicked point
+                    let target = track.normalized_at(pos);
+                    self.drag_start_value = target;
+                    if self.glide_time.is_zero() {
+                        self.commit_normalized(ctx, target);
+                    } else {
+                        let from = self.normalized();
+                        self.glide = Some(Glide { from, to: target, elapsed: Duration::ZERO });
+                        ctx.request_anim_frame();
                     }
                 }
             }
-            PointerEvent::Up(..) => {
-                if ctx.is_active() {
-                    ctx.release_pointer();
-                    self.drag_start_y = None;
+            PointerEvent::Move(PointerUpdate { current, .. }) => {
+                let Some(start) = self.drag_start else { return; };
+                if !ctx.is_active() {
+                    // Capture was lost without an Up or Cancel
+                    self.end_gesture(ctx);
+                    return;
+                }
+                let pos = ctx.local_position(current.position);
+                // Dragging takes over from a glide, but a jittery
+                // click should not cut it short
+                if self.glide.is_some() && (pos - start).hypot() < DRAG_SLOP {
+                    return;
                 }
+                self.glide = None;
+                let delta = self.track(ctx.content_box_size()).normalized_delta(start, pos);
+                self.commit_normalized(ctx, self.drag_start_value + delta);
+            }
+            PointerEvent::Scroll(PointerScrollEvent { delta, state, .. }) => {
+                self.stop_glide(ctx);
+                let fine = state.modifiers.shift().then_some(KEY_STEP_DB / FINE_STEP_DB);
+                self.control.on_scroll(ctx, delta, fine, None);
             }
             _ => {}
         }
     }
 
+    fn on_text_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &TextEvent,
+    ) {
+        if ctx.is_disabled() {
+            return;
+        }
+        // The keyboard takes over from a glide
+        self.stop_glide(ctx);
+        self.control.on_text_event(ctx, event, None);
+    }
+
+    fn on_anim_frame(&mut self, ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, interval: u64) {
+        let Some(glide) = self.glide.as_mut() else { return; };
+        glide.elapsed += Duration::from_nanos(interval);
+        let t = (glide.elapsed.as_secs_f64() / self.glide_time.as_secs_f64()).min(1.0);
+        // Ease out: fast start, gentle landing
+        let eased = 1.0 - (1.0 - t).powi(3);
+        let norm = glide.from + (glide.to - glide.from) * eased;
+        self.control.value = self.control.value_at(norm);
+        ctx.submit_action::<ControlAction<f64>>(ControlAction::Changed(self.control.value));
+        ctx.request_render();
+        if self.control.readout.invalidate() {
+            ctx.request_layout();
+        }
+        if t < 1.0 {
+            ctx.request_anim_frame();
+        } else {
+            self.glide = None;
+            if self.end_after_glide {
+                self.end_after_glide = false;
+                self.in_gesture = false;
+                ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureEnd);
+            }
+        }
+    }
+
     fn accepts_pointer_interaction(&self) -> bool {
         true
     }
@@ -184,121 +414,83 @@ impl Widget for Fader {
 
     fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {}
 
-    fn update(&mut self, _ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, _event: &Update) {}
+    fn update(&mut self, ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, event: &Update) {
+        match event {
+            Update::HoveredChanged(_) if self.control.readout.is_enabled() => ctx.request_render(),
+            Update::FocusChanged(false) if self.control.entry.is_open() => {
+                self.control.entry.close();
+                ctx.request_render();
+            }
+            Update::DisabledChanged(true) | Update::StashedChanged(true) => {
+                // The pointer capture goes with it; close the gesture too
+                self.drag_start = None;
+                self.glide = None;
+                self.end_after_glide = false;
+                if self.in_gesture {
+                    self.in_gesture = false;
+                    ctx.request_render();
+                    ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureEnd);
+                }
+            }
+            _ => {}
+        }
+    }
 
     fn measure(
         &mut self,
         _ctx: &mut MeasureCtx<'_>,
         _props: &PropertiesRef<'_>,
         axis: Axis,
-        _len_req: LenReq,
+        len_req: LenReq,
         _cross_length: Option<f64>,
     ) -> f64 {
-        match axis {
-            Axis::Horizontal => FADER_WIDTH,
-            Axis::Vertical => FADER_HEIGHT,
-        }
+        track::measure(self.orientation, axis, len_req, self.length, THICKNESS)
     }
 
     fn layout(
         &mut self,
-        _ctx: &mut LayoutCtx<'_>,
+        ctx: &mut LayoutCtx<'_>,
         _props: &PropertiesRef<'_>,
         _size: Size,
     ) {
+        self.control.readout.rebuild(ctx.text_contexts(), self.control.value);
+        self.control.entry.rebuild(ctx.text_contexts());
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx<'_>, _props: &PropertiesRef<'_>, painter: &mut Painter<'_>) {
-        let size = ctx.content_box_size();
-        let cx = size.width / 2.0;
-        let (track_top, track_bottom) = Self::track_range(size.height);
-
-        // Track groove
-        let track_rect = Rect::new(
-            cx - TRACK_WIDTH / 2.0,
-            track_top,
-            cx + TRACK_WIDTH / 2.0,
-            track_bottom,
-        );
-        let track_rr = RoundedRect::from_rect(track_rect, TRACK_WIDTH / 2.0);
-        painter.fill(track_rr, Color::from_rgb8(0x30, 0x30, 0x30)).fill_rule(Fill::NonZero).draw();
+        let track = self.track(ctx.content_box_size());
+        track.paint_groove(painter);
 
         // dB scale marks
         let mark_color = Color::from_rgb8(0x70, 0x70, 0x70);
         let mark_stroke = Stroke::new(1.0);
         let db_marks = [0.0, -6.0, -24.0, -48.0];
         for &db in &db_marks {
-            if db >= self.min_db && db <= self.max_db {
-                let norm = Self::db_to_normalized(db, self.min_db, self.max_db);
-                let y = track_bottom - norm * (track_bottom - track_top);
-                let left = cx - TRACK_WIDTH / 2.0 - 6.0;
-                let right = cx - TRACK_WIDTH / 2.0 - 2.0;
-                painter.stroke(Line::new(Point::new(left, y), Point::new(right, y)), &mark_stroke, mark_color).draw();
+            if db >= self.control.min && db <= self.control.max {
+                let norm = self.normalized_of(db);
+                track.paint_tick(
+                    painter, norm,
+                    -TRACK_WIDTH / 2.0 - 6.0, -TRACK_WIDTH / 2.0 - 2.0,
+                    &mark_stroke, mark_color,
+                );
             }
         }
 
         // Prominent default mark at -12 dB
-        {
-            let norm = Self::db_to_normalized(self.default_db, self.min_db, self.max_db);
-            let y = track_bottom - norm * (track_bottom - track_top);
-            let default_color = Color::from_rgb8(0xB0, 0xB0, 0xB0);
-            let default_stroke = Stroke::new(1.5);
-            // Left tick
-            painter.stroke(
-                Line::new(Point::new(cx - TRACK_WIDTH / 2.0 - 8.0, y),
-                           Point::new(cx - TRACK_WIDTH / 2.0 - 1.0, y)),
-                &default_stroke, default_color,
-            ).draw();
-            // Right tick
-            painter.stroke(
-                Line::new(Point::new(cx + TRACK_WIDTH / 2.0 + 1.0, y),
-                           Point::new(cx + TRACK_WIDTH / 2.0 + 8.0, y)),
-                &default_stroke, default_color,
-            ).draw();
-        }
+        track.paint_default_mark(painter, self.normalized_of(self.control.default));
 
-        // Lit fill from bottom to grip
-        let grip_y = self.grip_y(size.height);
-        if grip_y < track_bottom {
-            let lit_rect = Rect::new(
-                cx - TRACK_WIDTH / 2.0 + 0.5,
-                grip_y,
-                cx + TRACK_WIDTH / 2.0 - 0.5,
-                track_bottom,
-            );
-            painter.fill(lit_rect, self.tint).fill_rule(Fill::NonZero).draw();
-        }
+        // Lit fill from the minimum end to the grip
+        let norm = self.normalized();
+        track.paint_fill(painter, 0.0, norm, self.tint);
 
-        // Grip knob
-        let grip_rect = Rect::new(
-            cx - GRIP_WIDTH / 2.0,
-            grip_y - GRIP_HEIGHT / 2.0,
-            cx + GRIP_WIDTH / 2.0,
-            grip_y + GRIP_HEIGHT / 2.0,
-        );
-        let grip_rr = RoundedRect::from_rect(grip_rect, GRIP_RADIUS);
-        let grip_color = if ctx.is_active() {
-            Color::from_rgb8(0x90, 0x90, 0x90)
-        } else if ctx.is_hovered() {
-            Color::from_rgb8(0x80, 0x80, 0x80)
-        } else {
-            Color::from_rgb8(0x6A, 0x6A, 0x6A)
-        };
-        painter.fill(grip_rr, grip_color).fill_rule(Fill::NonZero).draw();
-        painter.stroke(grip_rr, &Stroke::new(1.0), Color::from_rgb8(0xA0, 0xA0, 0xA0)).draw();
-
-        // Grip lines (texture)
-        let line_stroke = Stroke::new(0.5).with_caps(Cap::Butt);
-        let line_color = Color::from_rgb8(0x50, 0x50, 0x50);
-        for i in [-2.0, 0.0, 2.0] {
-            let y = grip_y + i;
-            painter.stroke(
-                Line::new(
-                    Point::new(cx - GRIP_WIDTH / 2.0 + 4.0, y),
-                    Point::new(cx + GRIP_WIDTH / 2.0 - 4.0, y),
-                ),
-                &line_stroke, line_color,
-            ).draw();
+        track.paint_grip(painter, norm, ctx.is_active(), ctx.is_hovered());
+
+        // Text entry field, or value readout while interacting
+        let grip_center = track.point(norm, 0.0);
+        if self.control.entry.is_open() {
+            self.control.entry.paint(painter, grip_center, self.tint);
+        } else if ctx.is_hovered() || ctx.is_active() {
+            self.control.readout.paint(painter, grip_center);
         }
     }
 
@@ -312,10 +504,9 @@ impl Widget for Fader {
         _props: &PropertiesRef<'_>,
         node: &mut Node,
     ) {
-        let db = self.current_db();
-        node.set_numeric_value(db);
-        node.set_min_numeric_value(self.min_db);
-        node.set_max_numeric_value(self.max_db);
+        node.set_numeric_value(self.control.value);
+        node.set_min_numeric_value(self.control.min);
+        node.set_max_numeric_value(self.control.max);
     }
 
     fn children_ids(&self) -> SmallVec<[WidgetId; 16]> {
@@ -0,0 +1,24 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+/// Action emitted by interactive controls.
+///
+/// Besides value changes, controls report when the user grabs and releases
+/// them, like VST3/CLAP `begin_edit`/`end_edit`. Every `Changed` made by the
+/// user is bracketed by `GestureBegin` and `GestureEnd`: a drag spans one
+/// gesture, while one-off edits (keys, scroll, reset, text entry) each form
+/// a complete gesture of their own. A drag whose pointer is cancelled, or
+/// whose control is disabled or stashed, still ends with `GestureEnd`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ControlAction<T> {
+    /// The user grabbed the control.
+    GestureBegin,
+    /// The value changed.
+    Changed(T),
+    /// The user released the control.
+    GestureEnd,
+}
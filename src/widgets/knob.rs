@@ -8,10 +8,11 @@
 use std::f64::consts::PI;
 
 use xilem::masonry::accesskit::{Node, Role};
+use xilem::masonry::core::keyboard::Modifiers;
 use xilem::masonry::core::{
     AccessCtx, EventCtx, LayoutCtx, MeasureCtx, PaintCtx, PointerButtonEvent, PointerEvent,
-    PointerUpdate, PropertiesMut, PropertiesRef, RegisterCtx, Update, UpdateCtx, Widget, WidgetId,
-    WidgetMut,
+    PointerScrollEvent, PointerUpdate, PropertiesMut, PropertiesRef, RegisterCtx, TextEvent,
+    Update, UpdateCtx, Widget, WidgetId, WidgetMut,
 };
 use xilem::masonry::imaging::Painter;
 use xilem::masonry::kurbo::{Arc, Axis, Cap, Circle, Line, Point, Size, Stroke, Vec2};
@@ -21,7 +22,12 @@ use xilem::masonry::peniko::{Color, Fill};
 use smallvec::SmallVec;
 use tracing::trace_span;
 
+use crate::taper::Taper;
 use crate::theme::DEFAULT_TINT;
+use crate::widgets::control::{ControlValue, KEY_FRACTION};
+use crate::widgets::entry::EntryGesture;
+use crate::widgets::gesture::ControlAction;
+use crate::widgets::readout::ValueFormatter;
 
 const KNOB_RADIUS: f64 = 18.0;
 const KNOB_RADIUS_SMALL: f64 = 11.0;
@@ -31,52 +37,149 @@ const INDICATOR_WIDTH: f64 = 2.5;
 const INDICATOR_WIDTH_SMALL: f64 = 1.5;
 const ARC_START: f64 = 0.75 * PI;
 const ARC_SWEEP: f64 = 1.5 * PI;
+/// Default drag distance in pixels that sweeps the whole range.
+const DEFAULT_PIXELS_PER_RANGE: f64 = 200.0;
+/// Default divisor applied to drag and scroll while the fine modifier is held.
+const DEFAULT_FINE_DIVISOR: f64 = 10.0;
+/// Default detent capture zone, as a fraction of the knob's travel either side.
+const DEFAULT_DETENT_WIDTH: f64 = 0.03;
+/// A held detent is only released once the drag leaves this multiple of its width.
+const DETENT_RELEASE_FACTOR: f64 = 2.0;
 
 /// A rotary knob widget with a lit color ring showing the value range.
+///
+/// When focused, the knob responds to the keyboard: arrow keys nudge the
+/// value by one step (or 1% of its travel), PageUp/PageDown move ten times
+/// as far, Home/End jump to min/max, and Delete/Backspace reset to default.
+/// Scrolling over the knob moves it by the same increment per wheel notch.
+///
+/// Dragging sweeps the full range over `pixels_per_range` pixels (200 by
+/// default). Holding the fine modifier (Shift by default) divides both drag
+/// and scroll movement by `fine_divisor`, so wide ranges like a 20–20000 Hz
+/// cutoff can still be set precisely.
+///
+/// All positional behavior — indicator angle, lit arc, drag and unstepped
+/// key/scroll increments — goes through the knob's [`Taper`], so a `Log`
+/// taper spreads a frequency range evenly by octaves.
+///
+/// An optional detent turns the knob bipolar: the lit arc grows from the
+/// detent value, a tick marks it on the ring, and dragging near it snaps
+/// magnetically onto it. Releasing the snap takes twice the capture
+/// distance, so the value doesn't flicker on and off the detent.
+///
+/// With a readout formatter set, the formatted value is shown over the
+/// knob while it is hovered or dragged.
+///
+/// An [`EntryGesture`] can open an inline text field for typing an exact
+/// value (Enter also opens it while focused). The typed value is clamped
+/// and quantized like any other input; Escape or losing focus cancels.
+///
+/// Emits [`ControlAction`]s: value changes bracketed by gesture begin/end.
 pub struct Knob {
-    value: f64,
-    min: f64,
-    max: f64,
-    default: f64,
-    step: f64,
+    control: ControlValue,
     tint: Color,
     small: bool,
-    drag_start_y: Option<f64>,
-    drag_start_value: f64,
+    pixels_per_range: f64,
+    fine_modifier: Modifiers,
+    fine_divisor: f64,
+    /// Pointer y at the previous drag event; `None` when not dragging.
+    drag_last_y: Option<f64>,
+    /// Unquantized position (0..1, after taper) accumulated during a drag.
+    drag_norm: f64,
+    /// Bipolar centre value the knob snaps to while dragging.
+    detent: Option<f64>,
+    /// Capture zone either side of the detent, as a fraction of travel.
+    detent_width: f64,
+    /// Whether the current drag is held on the detent.
+    detent_held: bool,
+    /// Whether the detent may capture the drag by proximity; cleared on
+    /// release until the drag has left the capture zone.
+    detent_armed: bool,
 }
 
 impl Knob {
     pub fn new(min: f64, max: f64, value: f64, default: f64) -> Self {
         Self {
-            value: value.clamp(min, max),
-            min,
-            max,
-            default: default.clamp(min, max),
-            step: 0.0,
+            control: ControlValue::new(min, max, value, default),
             tint: DEFAULT_TINT,
             small: false,
-            drag_start_y: None,
-            drag_start_value: 0.0,
+            pixels_per_range: DEFAULT_PIXELS_PER_RANGE,
+            fine_modifier: Modifiers::SHIFT,
+            fine_divisor: DEFAULT_FINE_DIVISOR,
+            drag_last_y: None,
+            drag_norm: 0.0,
+            detent: None,
+            detent_width: DEFAULT_DETENT_WIDTH,
+            detent_held: false,
+            detent_armed: true,
         }
     }
 
-    pub fn with_step(mut self, step: f64) -> Self { self.step = step; self }
+    pub fn with_step(mut self, step: f64) -> Self { self.control.step = step; self }
     pub fn with_tint(mut self, color: Color) -> Self { self.tint = color; self }
     pub fn with_small(mut self, small: bool) -> Self { self.small = small; self }
+    pub fn with_taper(mut self, taper: Taper) -> Self { self.control.taper = taper; self }
+
+    /// Make the knob bipolar around `value`, with a magnetic detent there.
+    pub fn with_detent(mut self, value: f64) -> Self {
+        self.detent = Some(value);
+        self
+    }
+
+    /// Show the value, formatted by `formatter`, while hovered or dragged.
+    pub fn with_readout(mut self, formatter: ValueFormatter) -> Self {
+        let value = self.control.value;
+        self.control.readout.set_formatter(Some(formatter), value);
+        self
+    }
+
+    /// Set the gesture that opens inline text entry.
+    pub fn with_entry_gesture(mut self, gesture: EntryGesture) -> Self {
+        self.control.entry_gesture = gesture;
+        self
+    }
+
+    /// Set the detent capture zone either side, as a fraction of travel.
+    pub fn with_detent_width(mut self, width: f64) -> Self {
+        self.detent_width = width.clamp(0.0, 0.5);
+        self
+    }
+
+    /// Set the vertical drag distance (in pixels) that sweeps the whole range.
+    pub fn with_pixels_per_range(mut self, pixels: f64) -> Self {
+        self.pixels_per_range = pixels.max(1.0);
+        self
+    }
+
+    /// Set the modifier that enables fine adjustment. `Modifiers::empty()`
+    /// disables fine mode.
+    pub fn with_fine_modifier(mut self, modifier: Modifiers) -> Self {
+        self.fine_modifier = modifier;
+        self
+    }
+
+    /// Set how much finer drag and scroll become while the modifier is held.
+    pub fn with_fine_divisor(mut self, divisor: f64) -> Self {
+        self.fine_divisor = divisor.max(1.0);
+        self
+    }
 
     pub fn set_value(this: &mut WidgetMut<'_, Self>, value: f64) {
-        let v = value.clamp(this.widget.min, this.widget.max);
-        if (this.widget.value - v).abs() > f64::EPSILON {
-            this.widget.value = v;
+        let v = this.widget.control.clamp(value);
+        if (this.widget.control.value - v).abs() > f64::EPSILON {
+            this.widget.control.value = v;
             this.ctx.request_render();
+            if this.widget.control.readout.invalidate() { this.ctx.request_layout(); }
         }
     }
 
     pub fn set_range(this: &mut WidgetMut<'_, Self>, min: f64, max: f64) {
-        this.widget.min = min;
-        this.widget.max = max;
-        this.widget.value = this.widget.value.clamp(min, max);
+        this.widget.control.min = min;
+        this.widget.control.max = max;
+        this.widget.control.value = this.widget.control.clamp(this.widget.control.value);
+        this.widget.control.default = this.widget.control.clamp(this.widget.control.default);
         this.ctx.request_render();
+        if this.widget.control.readout.invalidate() { this.ctx.request_layout(); }
     }
 
     pub fn set_tint(this: &mut WidgetMut<'_, Self>, color: Color) {
@@ -84,27 +187,101 @@ impl Knob {
         this.ctx.request_render();
     }
 
+    pub fn set_taper(this: &mut WidgetMut<'_, Self>, taper: Taper) {
+        this.widget.control.taper = taper;
+        this.ctx.request_render();
+    }
+
+    pub fn set_readout(this: &mut WidgetMut<'_, Self>, formatter: Option<ValueFormatter>) {
+        let value = this.widget.control.value;
+        if this.widget.control.readout.set_formatter(formatter, value) {
+            this.ctx.request_layout();
+        }
+    }
+
+    pub fn set_entry_gesture(this: &mut WidgetMut<'_, Self>, gesture: EntryGesture) {
+        this.widget.control.entry_gesture = gesture;
+        if gesture == EntryGesture::Disabled && this.widget.control.entry.is_open() {
+            this.widget.control.entry.close();
+            this.ctx.request_render();
+        }
+    }
+
+    pub fn set_detent(this: &mut WidgetMut<'_, Self>, detent: Option<f64>) {
+        this.widget.detent = detent;
+        this.widget.detent_held = false;
+        this.ctx.request_render();
+    }
+
+    pub fn set_detent_width(this: &mut WidgetMut<'_, Self>, width: f64) {
+        this.widget.detent_width = width.clamp(0.0, 0.5);
+    }
+
+    pub fn set_pixels_per_range(this: &mut WidgetMut<'_, Self>, pixels: f64) {
+        this.widget.pixels_per_range = pixels.max(1.0);
+    }
+
+    pub fn set_fine_modifier(this: &mut WidgetMut<'_, Self>, modifier: Modifiers) {
+        this.widget.fine_modifier = modifier;
+    }
+
+    pub fn set_fine_divisor(this: &mut WidgetMut<'_, Self>, divisor: f64) {
+        this.widget.fine_divisor = divisor.max(1.0);
+    }
+
     fn radius(&self) -> f64 { if self.small { KNOB_RADIUS_SMALL } else { KNOB_RADIUS } }
     fn ring_w(&self) -> f64 { if self.small { RING_WIDTH_SMALL } else { RING_WIDTH } }
     fn indicator_w(&self) -> f64 { if self.small { INDICATOR_WIDTH_SMALL } else { INDICATOR_WIDTH } }
 
     fn normalized(&self) -> f64 {
-        if (self.max - self.min).abs() < f64::EPSILON { return 0.0; }
-        (self.value - self.min) / (self.max - self.min)
+        self.control.normalized_of(self.control.value)
     }
 
     fn default_normalized(&self) -> f64 {
-        if (self.max - self.min).abs() < f64::EPSILON { return 0.0; }
-        (self.default - self.min) / (self.max - self.min)
+        self.control.normalized_of(self.control.default)
     }
 
-    fn quantize(&self, val: f64) -> f64 {
-        if self.step > 0.0 {
-            let steps = ((val - self.min) / self.step).round();
-            (self.min + steps * self.step).clamp(self.min, self.max)
-        } else {
-            val.clamp(self.min, self.max)
+    fn value_at(&self, norm: f64) -> f64 {
+        self.control.value_at(norm)
+    }
+
+    fn detent_normalized(&self) -> Option<f64> {
+        self.detent.map(|d| self.control.normalized_of(d))
+    }
+
+    /// Apply the magnetic detent to the drag, which just moved from
+    /// `prev_norm` to `drag_norm`. Returns the detent value while held.
+    ///
+    /// The detent captures within `detent_width`, or when the drag
+    /// crosses it, and releases beyond twice that. The travel spent
+    /// pulling free is dropped on release, so the value carries on from
+    /// the detent instead of jumping, and proximity only captures again
+    /// once the drag has left the zone.
+    fn snap_drag(&mut self, prev_norm: f64) -> Option<f64> {
+        let det_n = self.detent_normalized()?;
+        let offset = self.drag_norm - det_n;
+        let dist = offset.abs();
+        if self.detent_held {
+            let release = self.detent_width * DETENT_RELEASE_FACTOR;
+            if dist <= release {
+                return self.detent;
+            }
+            self.detent_held = false;
+            self.detent_armed = false;
+            self.drag_norm = det_n + offset.signum() * (dist - release);
+            return None;
+        }
+        if dist >= self.detent_width {
+            self.detent_armed = true;
         }
+        let crossed = (prev_norm - det_n) * offset < 0.0;
+        self.detent_held = crossed || (self.detent_armed && dist < self.detent_width);
+        if self.detent_held { self.detent } else { None }
+    }
+
+    /// Whether the configured fine modifier is held.
+    fn is_fine(&self, modifiers: Modifiers) -> bool {
+        !self.fine_modifier.is_empty() && modifiers.contains(self.fine_modifier)
     }
 
     fn angle_for_normalized(n: f64) -> f64 {
@@ -113,7 +290,7 @@ impl Knob {
 }
 
 impl Widget for Knob {
-    type Action = f64;
+    type Action = ControlAction<f64>;
 
     fn on_pointer_event(
         &mut self,
@@ -121,52 +298,106 @@ impl Widget for Knob {
         _props: &mut PropertiesMut<'_>,
         event: &PointerEvent,
     ) {
+        // Releases are handled even when disabled, so a drag always ends
+        if let PointerEvent::Up(..) | PointerEvent::Cancel(..) = event {
+            self.control.end_drag(ctx);
+            self.drag_last_y = None;
+            return;
+        }
         if ctx.is_disabled() { return; }
         match event {
             PointerEvent::Down(PointerButtonEvent { state, .. }) => {
                 ctx.request_focus();
+                if self.control.entry.is_open() {
+                    // Clicking the control again abandons the typed text
+                    self.control.entry.close();
+                    ctx.request_render();
+                    return;
+                }
+                if self.control.entry_gesture.opens_on(state.count, state.modifiers) {
+                    self.control.entry.open();
+                    ctx.request_layout();
+                    ctx.request_render();
+                    return;
+                }
                 if state.count == 2 {
                     // Double-click: reset to default
-                    self.value = self.default;
-                    ctx.submit_action::<f64>(self.value);
+                    self.control.value = self.control.default;
+                    ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureBegin);
+                    ctx.submit_action::<ControlAction<f64>>(ControlAction::Changed(self.control.value));
+                    ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureEnd);
                     ctx.request_render();
+                    if self.control.readout.invalidate() { ctx.request_layout(); }
                     return;
                 }
-                ctx.capture_pointer();
+                self.control.begin_drag(ctx);
                 let pos = ctx.local_position(state.position);
-                self.drag_start_y = Some(pos.y);
-                self.drag_start_value = self.value;
+                self.drag_last_y = Some(pos.y);
+                self.drag_norm = self.normalized();
+                self.detent_held = self.detent.is_some_and(|d| (self.control.value - d).abs() < f64::EPSILON);
+                self.detent_armed = true;
             }
             PointerEvent::Move(PointerUpdate { current, .. }) => {
-                if ctx.is_active() {
-                    if let Some(start_y) = self.drag_start_y {
-                        let pos = ctx.local_position(current.position);
-                        let dy = start_y - pos.y;
-                        let sensitivity = 0.005;
-                        let range = self.max - self.min;
-                        let new_val = self.quantize(self.drag_start_value + dy * sensitivity * range);
-                        if (self.value - new_val).abs() > f64::EPSILON {
-                            self.value = new_val;
-                            ctx.submit_action::<f64>(self.value);
-                            ctx.request_render();
-                        }
+                if !ctx.is_active() {
+                    // Capture was lost without an Up or Cancel
+                    self.control.end_drag(ctx);
+                    self.drag_last_y = None;
+                    return;
+                }
+                if let Some(last_y) = self.drag_last_y {
+                    // Integrate movement incrementally so pressing or
+                    // releasing the fine modifier mid-drag doesn't jump.
+                    let pos = ctx.local_position(current.position);
+                    let dy = last_y - pos.y;
+                    self.drag_last_y = Some(pos.y);
+                    let mut sensitivity = 1.0 / self.pixels_per_range;
+                    if self.is_fine(current.modifiers) {
+                        sensitivity /= self.fine_divisor;
+                    }
+                    let prev_norm = self.drag_norm;
+                    self.drag_norm = (prev_norm + dy * sensitivity).clamp(0.0, 1.0);
+                    match self.snap_drag(prev_norm) {
+                        // Detents needn't sit on the step grid
+                        Some(detent) => self.control.commit_exact(ctx, detent),
+                        None => self.control.commit(ctx, self.value_at(self.drag_norm)),
                     }
                 }
             }
-            PointerEvent::Up(..) => {
-                if ctx.is_active() {
-                    ctx.release_pointer();
-                    self.drag_start_y = None;
-                }
+            PointerEvent::Scroll(PointerScrollEvent { delta, state, .. }) => {
+                let fine = self.is_fine(state.modifiers).then_some(self.fine_divisor);
+                self.control.on_scroll(ctx, delta, fine, self.detent);
             }
             _ => {}
         }
     }
 
+    fn on_text_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &TextEvent,
+    ) {
+        if ctx.is_disabled() { return; }
+        self.control.on_text_event(ctx, event, self.detent);
+    }
+
     fn accepts_pointer_interaction(&self) -> bool { true }
     fn accepts_focus(&self) -> bool { true }
     fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {}
-    fn update(&mut self, _ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, _event: &Update) {}
+    fn update(&mut self, ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, event: &Update) {
+        match event {
+            Update::HoveredChanged(_) if self.control.readout.is_enabled() => ctx.request_render(),
+            Update::FocusChanged(false) if self.control.entry.is_open() => {
+                self.control.entry.close();
+                ctx.request_render();
+            }
+            Update::DisabledChanged(true) | Update::StashedChanged(true) => {
+                self.control.cancel_drag(ctx);
+                self.drag_last_y = None;
+            }
+            _ => {}
+        }
+    }
 
     fn measure(
         &mut self,
@@ -183,7 +414,9 @@ impl Widget for Knob {
         }
     }
 
-    fn layout(&mut self, _ctx: &mut LayoutCtx<'_>, _props: &PropertiesRef<'_>, _size: Size) {
+    fn layout(&mut self, ctx: &mut LayoutCtx<'_>, _props: &PropertiesRef<'_>, _size: Size) {
+        self.control.readout.rebuild(ctx.text_contexts(), self.control.value);
+        self.control.entry.rebuild(ctx.text_contexts());
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx<'_>, _props: &PropertiesRef<'_>, painter: &mut Painter<'_>) {
@@ -197,8 +430,8 @@ impl Widget for Knob {
         let track_arc = Arc::new(Point::new(cx, cy), Vec2::new(r, r), ARC_START, ARC_SWEEP, 0.0);
         painter.stroke(track_arc, &Stroke::new(ring_w).with_caps(Cap::Round), Color::from_rgb8(0x40, 0x40, 0x40)).draw();
 
-        // Lit arc from default to current value
-        let def_n = self.default_normalized();
+        // Lit arc from the detent (bipolar) or default to current value
+        let def_n = self.detent_normalized().unwrap_or_else(|| self.default_normalized());
         let cur_n = self.normalized();
         if (def_n - cur_n).abs() > 0.001 {
             let start = Self::angle_for_normalized(def_n.min(cur_n));
@@ -207,6 +440,17 @@ impl Widget for Knob {
             painter.stroke(lit_arc, &Stroke::new(ring_w).with_caps(Cap::Round), self.tint).draw();
         }
 
+        // Centre tick across the ring at the detent
+        if let Some(det_n) = self.detent_normalized() {
+            let dir = Vec2::from_angle(Self::angle_for_normalized(det_n));
+            let (r0, r1) = (r - ring_w, r + ring_w / 2.0 + 1.0);
+            let tick = Line::new(
+                Point::new(cx + dir.x * r0, cy + dir.y * r0),
+                Point::new(cx + dir.x * r1, cy + dir.y * r1),
+            );
+            painter.stroke(tick, &Stroke::new(1.5), Color::from_rgb8(0xB0, 0xB0, 0xB0)).draw();
+        }
+
         // Body
         let body_r = r - if self.small { 3.5 } else { 5.0 };
         let body = Circle::new(Point::new(cx, cy), body_r);
@@ -228,15 +472,30 @@ impl Widget for Knob {
         let p0 = Point::new(cx + dir.x * inner_r, cy + dir.y * inner_r);
         let p1 = Point::new(cx + dir.x * outer_r, cy + dir.y * outer_r);
         painter.stroke(Line::new(p0, p1), &Stroke::new(self.indicator_w()).with_caps(Cap::Round), Color::WHITE).draw();
+
+        // Text entry field, or value readout while interacting
+        if self.control.entry.is_open() {
+            self.control.entry.paint(painter, Point::new(cx, cy), self.tint);
+        } else if ctx.is_hovered() || ctx.is_active() {
+            self.control.readout.paint(painter, Point::new(cx, cy));
+        }
     }
 
     fn accessibility_role(&self) -> Role { Role::Slider }
 
     fn accessibility(&mut self, _ctx: &mut AccessCtx<'_>, _props: &PropertiesRef<'_>, node: &mut Node) {
-        node.set_numeric_value(self.value);
-        node.set_min_numeric_value(self.min);
-        node.set_max_numeric_value(self.max);
-        node.set_numeric_value_step(if self.step > 0.0 { self.step } else { 0.01 });
+        node.set_numeric_value(self.control.value);
+        node.set_min_numeric_value(self.control.min);
+        node.set_max_numeric_value(self.control.max);
+        // Unstepped knobs report the value distance of one arrow-key press
+        // at the current position, which varies along a non-linear taper.
+        let step = if self.control.step > 0.0 {
+            self.control.step
+        } else {
+            let n = self.normalized().min(1.0 - KEY_FRACTION);
+            (self.value_at(n + KEY_FRACTION) - self.value_at(n)).abs()
+        };
+        node.set_numeric_value_step(step);
     }
 
     fn children_ids(&self) -> SmallVec<[WidgetId; 16]> { SmallVec::new() }
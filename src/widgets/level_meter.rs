@@ -5,9 +5,11 @@
 //! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
 //! (compatible with the Xilem licence).
 
+use std::time::Duration;
+
 use xilem::masonry::accesskit::{Node, Role};
 use xilem::masonry::core::{
-    AccessCtx, EventCtx, LayoutCtx, MeasureCtx, PaintCtx, PointerEvent,
+    AccessCtx, EventCtx, LayoutCtx, MeasureCtx, PaintCtx, PointerButtonEvent, PointerEvent,
     PropertiesMut, PropertiesRef, RegisterCtx, Update, UpdateCtx, Widget, WidgetId,
     WidgetMut,
 };
@@ -20,7 +22,9 @@ use xilem::Color;
 use smallvec::SmallVec;
 use tracing::trace_span;
 
-/// Orientation of the level meter.
+use crate::widgets::scope::{SampleReader, ScopeReader, ScopeSource, claim_reader};
+
+/// Orientation of a level meter or fader.
 #[derive(Clone, Copy, PartialEq)]
 pub enum Orientation {
     Horizontal,
@@ -57,6 +61,13 @@ const BG_COLOR: Color = Color::from_rgb8(0x20, 0x20, 0x20);
 const GREEN: Color = Color::from_rgb8(0x30, 0xC0, 0x30);
 const ORANGE: Color = Color::from_rgb8(0xFF, 0x8C, 0x00);
 const RED: Color = Color::from_rgb8(0xE0, 0x20, 0x20);
+/// Clip indicator while nothing has clipped
+const CLIP_OFF: Color = Color::from_rgb8(0x40, 0x18, 0x18);
+/// Thickness of the peak-hold marker along the bar.
+const PEAK_MARKER: f64 = 2.0;
+/// Longest the clip indicator gets, as a fraction of the meter's length,
+/// so short, thick meters keep most of their length for the bar.
+const MAX_CLIP_FRACTION: f64 = 0.2;
 
 /// A power bar / level meter.
 ///
@@ -67,18 +78,92 @@ const RED: Color = Color::from_rgb8(0xE0, 0x20, 0x20);
 /// - `Tint(color)`: single solid color for the entire bar
 ///
 /// Can be horizontal (for transport bar) or vertical (for channel strips).
+///
+/// With a [`ScopeSource`] the meter reads the signal itself on animation
+/// frames and shows the peak of all samples since the previous frame:
+/// in dBFS on the `Db` scale, as a 0..1 amplitude on the `Linear` one.
+///
+/// Ballistics run in `on_anim_frame`, whether the level comes from a
+/// source or from [`LevelMeter::set_value`]:
+/// - Attack and release smooth the bar towards the input level
+/// - A peak-hold marker stays at the highest level for the hold time,
+///   then falls at a fixed rate
+/// - A clip indicator at the top end latches once the input reaches the
+///   clip level, until the meter is clicked
 pub struct LevelMeter {
+    /// Displayed level, following `target` with the ballistics
     value: f64,
+    /// Latest input level
+    target: f64,
     min: f64,
     max: f64,
     orientation: Orientation,
     style: MeterStyle,
     scale: MeterScale,
+    /// Time constants for rising and falling levels (zero = instant)
+    attack: Duration,
+    release: Duration,
+    /// Hold time and fall rate (units per second) of the peak marker
+    peak_hold: Option<(Duration, f64)>,
+    peak: f64,
+    /// Seconds since the peak was last raised
+    peak_age: f64,
+    /// Input level that latches the clip indicator
+    clip_level: Option<f64>,
+    clipped: bool,
+    /// Optional shared source for polling new data during anim frames
+    source: Option<ScopeSource>,
+    /// The source's reader, claimed on the first anim frame
+    reader: Option<ScopeReader>,
+    /// Another display held the source on the last try (already logged)
+    source_busy: bool,
 }
 
 impl LevelMeter {
     pub fn new(value: f64, min: f64, max: f64, orientation: Orientation) -> Self {
-        Self { value, min, max, orientation, style: MeterStyle::Gradient, scale: MeterScale::Db }
+        Self {
+            value, min, max, orientation,
+            target: value,
+            style: MeterStyle::Gradient,
+            scale: MeterScale::Db,
+            attack: Duration::ZERO,
+            release: Duration::ZERO,
+            peak_hold: None,
+            peak: value,
+            peak_age: 0.0,
+            clip_level: None,
+            clipped: false,
+            source: None,
+            reader: None,
+            source_busy: false,
+        }
+    }
+
+    /// Smooth the bar: `attack` and `release` are the time constants for
+    /// rising and falling levels.
+    pub fn with_ballistics(mut self, attack: Duration, release: Duration) -> Self {
+        self.attack = attack;
+        self.release = release;
+        self
+    }
+
+    /// Show a peak marker that holds for `hold`, then falls by `fall`
+    /// units (dB on the dB scale) per second.
+    pub fn with_peak_hold(mut self, hold: Duration, fall: f64) -> Self {
+        self.peak_hold = Some((hold, fall));
+        self
+    }
+
+    /// Show a clip indicator that latches when the input reaches `level`.
+    pub fn with_clip(mut self, level: f64) -> Self {
+        self.clip_level = Some(level);
+        self
+    }
+
+    /// Attach a shared source that the meter polls during animation frames.
+    pub fn with_source(mut self, source: ScopeSource) -> Self {
+        self.source = Some(source);
+        self
     }
 
     /// Set the visual style (gradient or tint).
@@ -100,10 +185,43 @@ impl LevelMeter {
     }
 
     pub fn set_value(this: &mut WidgetMut<'_, Self>, value: f64) {
-        if (this.widget.value - value).abs() > 0.001 {
+        let clipped = this.widget.clipped;
+        this.widget.input(value);
+        if this.widget.animated() {
+            this.ctx.request_anim_frame();
+        } else if (this.widget.value - value).abs() > 0.001 {
             this.widget.value = value;
             this.ctx.request_render();
         }
+        if this.widget.clipped != clipped {
+            this.ctx.request_render();
+        }
+    }
+
+    pub fn set_ballistics(this: &mut WidgetMut<'_, Self>, attack: Duration, release: Duration) {
+        this.widget.attack = attack;
+        this.widget.release = release;
+        this.ctx.request_anim_frame();
+    }
+
+    pub fn set_peak_hold(this: &mut WidgetMut<'_, Self>, peak_hold: Option<(Duration, f64)>) {
+        this.widget.peak_hold = peak_hold;
+        this.widget.peak = this.widget.value;
+        this.widget.peak_age = 0.0;
+        this.ctx.request_anim_frame();
+        this.ctx.request_render();
+    }
+
+    pub fn set_clip(this: &mut WidgetMut<'_, Self>, level: Option<f64>) {
+        this.widget.clip_level = level;
+        this.widget.clipped = false;
+        this.ctx.request_render();
+    }
+
+    /// Clear a latched clip indicator.
+    pub fn reset_clip(this: &mut WidgetMut<'_, Self>) {
+        this.widget.clipped = false;
+        this.ctx.request_render();
     }
 
     pub fn set_style(this: &mut WidgetMut<'_, Self>, style: MeterStyle) {
@@ -120,6 +238,14 @@ impl LevelMeter {
         }
     }
 
+    pub fn set_source(this: &mut WidgetMut<'_, Self>, source: ScopeSource) {
+        // Hand the old reader back before claiming the new one
+        this.widget.reader = None;
+        this.widget.source_busy = false;
+        this.widget.source = Some(source);
+        this.ctx.request_anim_frame();
+    }
+
     pub fn set_range(this: &mut WidgetMut<'_, Self>, min: f64, max: f64) {
         this.widget.min = min;
         this.widget.max = max;
@@ -151,21 +277,143 @@ impl LevelMeter {
         )
     }
 
-    fn normalized(&self) -> f64 {
+    /// Whether the display moves on its own between inputs.
+    fn animated(&self) -> bool {
+        !self.attack.is_zero() || !self.release.is_zero() || self.peak_hold.is_some()
+    }
+
+    /// Take a new input level: raise the peak marker and latch clipping.
+    fn input(&mut self, level: f64) {
+        self.target = level;
+        if self.clip_level.is_some_and(|clip| level >= clip) {
+            self.clipped = true;
+        }
+        if self.peak_hold.is_some() && level >= self.peak {
+            self.peak = level;
+            self.peak_age = 0.0;
+        }
+    }
+
+    /// Advance the ballistics by `dt` seconds. Returns true while the bar
+    /// or the peak marker is still moving.
+    fn advance(&mut self, dt: f64) -> bool {
+        let settle = (self.max - self.min).abs() * 1e-3;
+        let tau = if self.target > self.value { self.attack } else { self.release };
+        self.value = if tau.is_zero() {
+            self.target
+        } else {
+            self.value + (self.target - self.value) * (1.0 - (-dt / tau.as_secs_f64()).exp())
+        };
+        if (self.value - self.target).abs() < settle {
+            self.value = self.target;
+        }
+        let mut falling = false;
+        if let Some((hold, fall)) = self.peak_hold {
+            self.peak_age += dt;
+            if self.peak_age > hold.as_secs_f64() {
+                self.peak -= fall * dt;
+            }
+            // The marker rests on top of the bar
+            self.peak = self.peak.max(self.value);
+            falling = self.peak > self.value + settle;
+        }
+        self.value != self.target || falling
+    }
+
+    /// Thresholds of the orange and red zones as fractions of the range.
+    fn thresholds(&self) -> (f64, f64) {
+        match self.scale {
+            MeterScale::Db => {
+                let range = self.max - self.min;
+                (
+                    ((-12.0 - self.min) / range).clamp(0.0, 1.0),
+                    ((0.0 - self.min) / range).clamp(0.0, 1.0),
+                )
+            }
+            MeterScale::Linear => (0.75, 0.90),
+        }
+    }
+
+    /// Length of the bar along its axis, leaving room for the clip
+    /// indicator at the top end: a square, or shorter on thick meters.
+    fn bar_length(&self, size: Size) -> f64 {
+        let (length, cross) = match self.orientation {
+            Orientation::Horizontal => (size.width, size.height),
+            Orientation::Vertical => (size.height, size.width),
+        };
+        if self.clip_level.is_none() {
+            return length;
+        }
+        let clip = cross.min(length * MAX_CLIP_FRACTION);
+        (length - clip - 1.0).max(0.0)
+    }
+
+    /// Poll the source and return the peak of the new samples in the
+    /// meter's scale, if any arrived.
+    fn poll_level(&mut self) -> Option<f64> {
+        let mut reader = self.reader.take().or_else(|| {
+            self.source.as_ref().and_then(|s| claim_reader(s, &mut self.source_busy))
+        });
+        let peak = reader.as_mut().and_then(|r| r.poll()).map(|samples| {
+            samples.iter().fold(0.0_f32, |peak, s| peak.max(s.abs())) as f64
+        });
+        self.reader = reader;
+        let peak = peak?;
+        Some(match self.scale {
+            MeterScale::Db => (20.0 * peak.log10()).max(self.min),
+            MeterScale::Linear => peak,
+        })
+    }
+
+    fn normalized(&self, value: f64) -> f64 {
         let range = self.max - self.min;
         if range.abs() < f64::EPSILON { return 0.0; }
-        ((self.value - self.min) / range).clamp(0.0, 1.0)
+        ((value - self.min) / range).clamp(0.0, 1.0)
     }
 }
 
 impl Widget for LevelMeter {
     type Action = ();
 
-    fn on_pointer_event(&mut self, _: &mut EventCtx<'_>, _: &mut PropertiesMut<'_>, _: &PointerEvent) {}
-    fn accepts_pointer_interaction(&self) -> bool { false }
+    fn on_pointer_event(
+        &mut self, ctx: &mut EventCtx<'_>, _: &mut PropertiesMut<'_>, event: &PointerEvent,
+    ) {
+        // Click to clear a latched clip indicator
+        if let PointerEvent::Down(PointerButtonEvent { .. }) = event {
+            if self.clipped {
+                self.clipped = false;
+                ctx.request_render();
+            }
+        }
+    }
+    fn accepts_pointer_interaction(&self) -> bool { self.clip_level.is_some() }
     fn accepts_focus(&self) -> bool { false }
     fn register_children(&mut self, _: &mut RegisterCtx<'_>) {}
-    fn update(&mut self, _: &mut UpdateCtx<'_>, _: &mut PropertiesMut<'_>, _: &Update) {}
+
+    fn on_anim_frame(
+        &mut self, ctx: &mut UpdateCtx<'_>, _: &mut PropertiesMut<'_>, interval: u64,
+    ) {
+        let (value, peak, clipped) = (self.value, self.peak, self.clipped);
+        if let Some(level) = self.poll_level() {
+            self.input(level);
+        }
+        let moving = self.advance(interval as f64 * 1e-9);
+        if (self.value - value).abs() > 0.001
+            || (self.peak - peak).abs() > 0.001
+            || self.clipped != clipped
+        {
+            ctx.request_render();
+        }
+        if moving || self.source.is_some() {
+            ctx.request_anim_frame();
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<'_>, _: &mut PropertiesMut<'_>, event: &Update) {
+        if matches!(event, Update::WidgetAdded) && self.source.is_some() {
+            ctx.request_anim_frame();
+        }
+    }
 
     fn measure(
         &mut self, _: &mut MeasureCtx<'_>, _: &PropertiesRef<'_>,
@@ -183,25 +431,44 @@ impl Widget for LevelMeter {
 
     fn paint(&mut self, ctx: &mut PaintCtx<'_>, _: &PropertiesRef<'_>, painter: &mut Painter<'_>) {
         let size = ctx.content_box_size();
-        let norm = self.normalized();
+        let norm = self.normalized(self.value);
 
         // Background
         let bg_rect = Rect::new(0.0, 0.0, size.width, size.height);
         painter.fill(&bg_rect, BG_COLOR).fill_rule(Fill::NonZero).draw();
 
-        if norm < 0.001 { return; }
+        // Clip indicator at the top end of the bar
+        let bar = self.bar_length(size);
+        if self.clip_level.is_some() {
+            let r = match self.orientation {
+                Orientation::Horizontal => Rect::new(bar + 1.0, 0.0, size.width, size.height),
+                Orientation::Vertical => Rect::new(0.0, 0.0, size.width, size.height - bar - 1.0),
+            };
+            let color = if self.clipped { RED } else { CLIP_OFF };
+            painter.fill(&r, color).fill_rule(Fill::NonZero).draw();
+        }
 
         // Compute thresholds based on scale mode
-        let (threshold_norm, zero_norm) = match self.scale {
-            MeterScale::Db => {
-                let range = self.max - self.min;
-                (
-                    ((-12.0 - self.min) / range).clamp(0.0, 1.0),
-                    ((0.0 - self.min) / range).clamp(0.0, 1.0),
-                )
+        let (threshold_norm, zero_norm) = self.thresholds();
+
+        // Peak-hold marker, resting just beyond the bar's end
+        if self.peak_hold.is_some() {
+            let peak = self.normalized(self.peak);
+            if peak >= 0.001 {
+                let color = Self::interpolate_color(peak, threshold_norm, zero_norm);
+                let pos = (peak * bar).min(bar - PEAK_MARKER).max(0.0);
+                let r = match self.orientation {
+                    Orientation::Horizontal => Rect::new(pos, 0.0, pos + PEAK_MARKER, size.height),
+                    Orientation::Vertical => {
+                        let y = size.height - pos;
+                        Rect::new(0.0, y - PEAK_MARKER, size.width, y)
+                    }
+                };
+                painter.fill(&r, color).fill_rule(Fill::NonZero).draw();
             }
-            MeterScale::Linear => (0.75, 0.90),
-        };
+        }
+
+        if norm < 0.001 { return; }
 
         // For Tint mode: compute a single interpolated color based on fill level
         let tint_color = if self.style == MeterStyle::Tint {
@@ -212,14 +479,14 @@ impl Widget for LevelMeter {
 
         match self.orientation {
             Orientation::Horizontal => {
-                let fill_w = norm * size.width;
+                let fill_w = norm * bar;
 
                 if let Some(color) = tint_color {
                     let r = Rect::new(0.0, 0.0, fill_w, size.height);
                     painter.fill(&r, color).fill_rule(Fill::NonZero).draw();
                 } else {
-                    let thresh_x = threshold_norm * size.width;
-                    let zero_x = zero_norm * size.width;
+                    let thresh_x = threshold_norm * bar;
+                    let zero_x = zero_norm * bar;
                     // Green zone
                     let green_right = fill_w.min(thresh_x);
                     if green_right > 0.0 {
@@ -242,15 +509,15 @@ impl Widget for LevelMeter {
                 }
             }
             Orientation::Vertical => {
-                let fill_h = norm * size.height;
+                let fill_h = norm * bar;
                 let top = size.height - fill_h;
 
                 if let Some(color) = tint_color {
                     let r = Rect::new(0.0, top, size.width, size.height);
                     painter.fill(&r, color).fill_rule(Fill::NonZero).draw();
                 } else {
-                    let thresh_y = size.height - threshold_norm * size.height;
-                    let zero_y = size.height - zero_norm * size.height;
+                    let thresh_y = size.height - threshold_norm * bar;
+                    let zero_y = size.height - zero_norm * bar;
                     // Green zone (bottom)
                     let green_top = top.max(thresh_y);
                     if green_top < size.height {
@@ -5,7 +5,10 @@
 //! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
 //! (compatible with the Xilem licence).
 
+pub mod control;
+pub mod entry;
 pub mod fader;
+pub mod gesture;
 pub mod group_box;
 pub mod knob;
 pub mod param_selector;
@@ -13,3 +16,8 @@ pub mod push_button;
 pub mod scope;
 pub mod led;
 pub mod level_meter;
+pub mod readout;
+pub mod slider;
+pub mod spectrogram;
+pub mod spectrum;
+pub mod track;
@@ -19,16 +19,20 @@ use smallvec::SmallVec;
 use tracing::trace_span;
 
 use crate::theme::DEFAULT_TINT;
+use crate::widgets::gesture::ControlAction;
 
 const BUTTON_RADIUS: f64 = 8.0;
 
 /// A small circular push button for boolean on/off options.
 ///
 /// When active, shows a lit color. When inactive, shows a dark state.
-/// Clicking toggles the state and emits a `bool` action.
+/// Clicking toggles the state and emits a `bool` action, bracketed by
+/// gesture begin (press) and end (release).
 pub struct PushButton {
     active: bool,
     lit_color: Color,
+    /// Pressed, with the `GestureEnd` still owed.
+    pressed: bool,
 }
 
 impl PushButton {
@@ -36,6 +40,7 @@ impl PushButton {
         Self {
             active,
             lit_color: DEFAULT_TINT,
+            pressed: false,
         }
     }
 
@@ -55,10 +60,18 @@ impl PushButton {
         this.widget.lit_color = color;
         this.ctx.request_render();
     }
-}
+
+    /// Close the press gesture, if one is open.
+    fn end_press(&mut self, ctx: &mut EventCtx<'_>) {
+        if self.pressed {
+            self.pressed = false;
+            ctx.submit_action::<ControlAction<bool>>(ControlAction::GestureEnd);
+            ctx.request_render();
+        }
+    }
 
 impl Widget for PushButton {
-    type Action = bool;
+    type Action = ControlAction<bool>;
 
     fn on_pointer_event(
         &mut self,
@@ -66,22 +79,32 @@ impl Widget for PushButton {
         _props: &mut PropertiesMut<'_>,
         event: &PointerEvent,
     ) {
-        if ctx.is_disabled() {
-            return;
-        }
+        // Releases are handled even when disabled, so a press always ends
         match event {
-            PointerEvent::Down(..) => {
-                ctx.capture_pointer();
-                ctx.request_render();
-            }
             PointerEvent::Up(PointerButtonEvent { .. }) => {
-                if ctx.is_active() && ctx.is_hovered() {
-                    self.active = !self.active;
-                    ctx.submit_action::<bool>(self.active);
-                    ctx.request_render();
+                if self.pressed {
+                    if ctx.is_hovered() && !ctx.is_disabled() {
+                        self.active = !self.active;
+                        ctx.submit_action::<ControlAction<bool>>(ControlAction::Changed(self.active));
+                    }
+                    self.end_press(ctx);
                 }
                 ctx.release_pointer();
             }
+            PointerEvent::Cancel(..) => {
+                self.end_press(ctx);
+                ctx.release_pointer();
+            }
+            PointerEvent::Move(..) if self.pressed && !ctx.is_active() => {
+                // Capture was lost without an Up or Cancel
+                self.end_press(ctx);
+            }
+            PointerEvent::Down(..) if !ctx.is_disabled() => {
+                ctx.capture_pointer();
+                ctx.submit_action::<ControlAction<bool>>(ControlAction::GestureBegin);
+                self.pressed = true;
+                ctx.request_render();
+            }
             _ => {}
         }
     }
@@ -92,7 +115,15 @@ impl Widget for PushButton {
 
     fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {}
 
-    fn update(&mut self, _ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, _event: &Update) {}
+    fn update(&mut self, ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, event: &Update) {
+        let hidden = matches!(event, Update::DisabledChanged(true) | Update::StashedChanged(true));
+        if hidden && self.pressed {
+            // The pointer capture goes with it; close the gesture too
+            self.pressed = false;
+            ctx.submit_action::<ControlAction<bool>>(ControlAction::GestureEnd);
+            ctx.request_render();
+        }
+    }
 
     fn measure(
         &mut self,
@@ -0,0 +1,137 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use std::sync::Arc;
+
+use xilem::masonry::core::{BrushIndex, StyleProperty, render_text};
+use xilem::masonry::imaging::Painter;
+use xilem::masonry::kurbo::{Affine, Point, Rect, RoundedRect, Stroke};
+use xilem::masonry::parley::{FontContext, Layout, LayoutContext};
+use xilem::masonry::peniko::{Color, Fill};
+
+const FONT_SIZE: f32 = 10.0;
+const PAD_X: f64 = 4.0;
+const PAD_Y: f64 = 1.5;
+
+/// Formats a control value for display, e.g. `|v| format!("{v:.1} Hz")`.
+pub type ValueFormatter = Arc<dyn Fn(f64) -> String + Send + Sync>;
+
+/// A formatted value badge painted over a control while it is hovered
+/// or dragged.
+///
+/// The text layout is rebuilt during the widget's `layout` pass, so the
+/// owning widget must request a layout after calling [`Self::invalidate`].
+pub(crate) struct ValueReadout {
+    formatter: Option<ValueFormatter>,
+    /// Text shaped into `layout`
+    text: String,
+    layout: Option<Layout<BrushIndex>>,
+    dirty: bool,
+}
+
+impl ValueReadout {
+    pub(crate) fn new() -> Self {
+        Self { formatter: None, text: String::new(), layout: None, dirty: false }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.formatter.is_some()
+    }
+
+    /// Install a formatter, as every view rebuild does: `.readout(|v| ..)`
+    /// makes a new closure each time, so they can't be compared. Returns
+    /// true if the text shown for `value` changed, in which case the
+    /// owning widget must request a layout.
+    pub(crate) fn set_formatter(&mut self, formatter: Option<ValueFormatter>, value: f64) -> bool {
+        let Some(formatter) = formatter else {
+            self.layout = None;
+            self.text.clear();
+            return self.formatter.take().is_some();
+        };
+        let changed = self.formatter.is_none() || formatter(value) != self.text;
+        self.formatter = Some(formatter);
+        if changed {
+            self.dirty = true;
+        }
+        changed
+    }
+
+    /// Mark the text as stale. Returns true if a relayout is needed.
+    pub(crate) fn invalidate(&mut self) -> bool {
+        self.dirty = true;
+        self.is_enabled()
+    }
+
+    /// Rebuild the text layout for `value` if it is stale and the text
+    /// differs from what is shown.
+    pub(crate) fn rebuild(
+        &mut self,
+        contexts: (&mut FontContext, &mut LayoutContext<BrushIndex>),
+        value: f64,
+    ) {
+        if !self.dirty {
+            return;
+        }
+        self.dirty = false;
+        let Some(ref formatter) = self.formatter else { return; };
+        let text = formatter(value);
+        if self.layout.is_none() || text != self.text {
+            self.layout = Some(layout_text(contexts, &text));
+            self.text = text;
+        }
+    }
+
+    /// Paint the badge centered on `center`.
+    pub(crate) fn paint(&self, painter: &mut Painter<'_>, center: Point) {
+        if let Some(ref layout) = self.layout {
+            paint_badge(painter, layout, center, 0.0);
+        }
+    }
+}
+
+/// Shape a single line of badge text.
+pub(crate) fn layout_text(
+    (font_ctx, layout_ctx): (&mut FontContext, &mut LayoutContext<BrushIndex>),
+    text: &str,
+) -> Layout<BrushIndex> {
+    let mut builder = layout_ctx.ranged_builder(font_ctx, text, 1.0, true);
+    builder.push_default(StyleProperty::FontSize(FONT_SIZE));
+    let mut layout = builder.build(text);
+    layout.break_all_lines(None);
+    layout
+}
+
+/// Paint `layout` on a dark rounded badge centered on `center`, at least
+/// `min_width` wide. Returns the text's bounding rect.
+pub(crate) fn paint_badge(
+    painter: &mut Painter<'_>,
+    layout: &Layout<BrushIndex>,
+    center: Point,
+    min_width: f64,
+) -> Rect {
+    let text_w = layout.width() as f64;
+    let text_h = (layout.height() as f64).max(FONT_SIZE as f64);
+    let half_w = (text_w / 2.0 + PAD_X).max(min_width / 2.0);
+    let rect = Rect::new(
+        center.x - half_w,
+        center.y - text_h / 2.0 - PAD_Y,
+        center.x + half_w,
+        center.y + text_h / 2.0 + PAD_Y,
+    );
+    let rr = RoundedRect::from_rect(rect, 3.0);
+    painter.fill(rr, Color::from_rgba8(0x18, 0x18, 0x18, 0xE0)).fill_rule(Fill::NonZero).draw();
+    painter.stroke(rr, &Stroke::new(0.5), Color::from_rgb8(0x70, 0x70, 0x70)).draw();
+    let text_origin = Point::new(center.x - text_w / 2.0, center.y - text_h / 2.0);
+    render_text(
+        painter,
+        Affine::translate(text_origin.to_vec2()),
+        layout,
+        &[Color::from_rgb8(0xEE, 0xEE, 0xEE).into()],
+        true,
+    );
+    Rect::from_origin_size(text_origin, (text_w, text_h))
+}
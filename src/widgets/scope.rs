@@ -5,158 +5,596 @@
 //! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
 //! (compatible with the Xilem licence).
 
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use xilem::masonry::accesskit::{Node, Role};
 use xilem::masonry::core::{
-    AccessCtx, EventCtx, LayoutCtx, MeasureCtx, PaintCtx, PointerEvent, PropertiesMut,
-    PropertiesRef, RegisterCtx, Update, UpdateCtx, Widget, WidgetId, WidgetMut,
+    AccessCtx, BrushIndex, EventCtx, LayoutCtx, MeasureCtx, PaintCtx, PointerButton,
+    PointerButtonEvent, PointerEvent, PointerScrollEvent, PointerUpdate, PropertiesMut,
+    PropertiesRef, RegisterCtx, ScrollDelta, Update, UpdateCtx, Widget, WidgetId, WidgetMut,
+    render_text,
 };
 use xilem::masonry::imaging::Painter;
 use xilem::masonry::kurbo::{
-    Axis, BezPath, Cap, Line, Point, Rect, RoundedRect, Size, Stroke,
+    Affine, Axis, BezPath, Cap, Line, Point, Rect, RoundedRect, Size, Stroke,
 };
 use xilem::masonry::layout::LenReq;
+use xilem::masonry::parley::Layout;
 use xilem::masonry::peniko::{Color, Fill};
 
 use smallvec::SmallVec;
 use tracing::trace_span;
 
+use crate::widgets::readout::layout_text;
+
+/// Preferred size, used when the parent leaves the choice to the scope.
 const SCOPE_WIDTH: f64 = 192.0;
 const SCOPE_HEIGHT: f64 = 196.0;
+/// Smallest size that still shows a readable trace.
+const MIN_WIDTH: f64 = 48.0;
+const MIN_HEIGHT: f64 = 32.0;
 const BORDER_RADIUS: f64 = 4.0;
 const PADDING: f64 = 2.0;
+/// Headroom between full scale and the top/bottom of the display.
+const TRACE_INSET: f64 = 2.0;
+const DEFAULT_SAMPLE_RATE: f64 = 48_000.0;
+/// Number of horizontal (time) divisions; one per vertical grid line.
+const TIME_DIVISIONS: usize = 4;
+/// Longest stretch of signal kept for triggering, in seconds.
+const MAX_RAW_SECS: f64 = 10.0;
+const LABEL_COLOR: Color = Color::from_rgb8(0x50, 0x80, 0x50);
+/// In Auto mode, free-run after this long without a trigger.
+const AUTO_TIMEOUT_SECS: f64 = 0.1;
+/// Pointer travel (in pixels) below which a press and release is a click.
+const CLICK_SLOP: f64 = 3.0;
+/// Drag distance (in pixels) that zooms by a factor of e.
+const ZOOM_DRAG_PIXELS: f64 = 100.0;
+/// Zoom factor per mouse-wheel notch.
+const ZOOM_PER_NOTCH: f64 = 1.25;
+/// Trackpad pixels that count as one mouse-wheel notch.
+const SCROLL_PIXELS_PER_NOTCH: f64 = 20.0;
+const MAX_TIME_ZOOM: f64 = 64.0;
+const MAX_AMP_ZOOM: f64 = 32.0;
+const CURSOR_COLOR: Color = Color::from_rgb8(0xE0, 0xE0, 0xE0);
+/// Vertical distance (in pixels) within which the trigger marker can be grabbed.
+const MARKER_GRAB: f64 = 5.0;
+const TRIGGER_COLOR: Color = Color::from_rgb8(0xFF, 0xA0, 0x30);
+/// Most earlier traces kept for phosphor persistence.
+const MAX_AFTERGLOW: usize = 48;
+/// Opacity of a fresh afterglow trace; overlapping traces add up, so
+/// often-visited paths glow brighter.
+const AFTERGLOW_ALPHA: f32 = 0.35;
+/// Default persistence of XY traces.
+const DEFAULT_XY_PERSISTENCE: Duration = Duration::from_millis(150);
+/// Most points plotted per XY frame; longer buffers are thinned out.
+const MAX_XY_POINTS: usize = 2048;
+/// Weight of the previous correlation reading when a new buffer arrives.
+const CORRELATION_SMOOTHING: f32 = 0.8;
+const CORRELATION_HEIGHT: f64 = 6.0;
+const CORRELATION_POSITIVE: Color = Color::from_rgb8(0x00, 0xC0, 0x60);
+const CORRELATION_NEGATIVE: Color = Color::from_rgb8(0xE0, 0x40, 0x30);
+const STOPPED_COLOR: Color = Color::from_rgb8(0x80, 0x80, 0x80);
+/// Trace colors for channels without an explicit color, cycled.
+const DEFAULT_TRACE_COLORS: [Color; 4] = [
+    Color::from_rgb8(0x00, 0xFF, 0x80),
+    Color::from_rgb8(0x40, 0xC0, 0xFF),
+    Color::from_rgb8(0xFF, 0x60, 0xC0),
+    Color::from_rgb8(0xFF, 0xE0, 0x60),
+];
 
 /// Thread-safe buffer for passing audio samples to the scope.
 ///
 /// Wrap your sample data in `Arc<Vec<f32>>` and send it from any thread.
 /// The scope will decimate the data for display and only keep the
 /// last buffer for rendering efficiency.
+///
+/// Multi-channel data is stored as interleaved frames: for stereo,
+/// `[L0, R0, L1, R1, ...]`. A trailing partial frame is ignored.
 #[derive(Clone)]
 pub struct ScopeBuffer {
     pub samples: Arc<Vec<f32>>,
+    /// Number of interleaved channels (at least 1).
+    pub channels: usize,
 }
 
 impl ScopeBuffer {
     pub fn new(samples: Vec<f32>) -> Self {
         Self {
             samples: Arc::new(samples),
+            channels: 1,
         }
     }
 
     pub fn from_arc(samples: Arc<Vec<f32>>) -> Self {
-        Self { samples }
+        Self { samples, channels: 1 }
+    }
+
+    /// Wrap interleaved frames of `channels` channels.
+    pub fn interleaved(samples: Vec<f32>, channels: usize) -> Self {
+        Self {
+            samples: Arc::new(samples),
+            channels: channels.max(1),
+        }
+    }
+
+    /// Number of complete frames in the buffer.
+    pub fn frames(&self) -> usize {
+        self.samples.len() / self.channels.max(1)
+    }
+}
+
+/// Reading end of a sample stream, polled by a display once per
+/// animation frame.
+///
+/// Implemented for the triple-buffer output behind
+/// [`ScopeSource::new`], which only ever yields the latest published
+/// block, and for the gapless [`RingConsumer`](crate::RingConsumer).
+/// Wrap any implementation with [`ScopeSource::from_reader`] to feed a
+/// scope, spectrum, spectrogram or level meter.
+pub trait SampleReader: Send {
+    /// Number of interleaved channels per frame (at least 1).
+    fn channels(&self) -> usize;
+
+    /// Interleaved samples that arrived since the last poll, if any.
+    /// Called from the UI thread; must not block.
+    fn poll(&mut self) -> Option<&[f32]>;
+    /// Whether the next poll's samples will carry on directly from the
+    /// last poll's, as with a ring that hasn't overflowed. Analyzers only
+    /// join consecutive blocks while this holds.
+    fn is_gapless(&self) -> bool {
+        false
+    }
+}
+
+/// Latest-block reader over a triple buffer.
+struct TripleBufferReader {
+    output: triple_buffer::Output<Vec<f32>>,
+    channels: usize,
+}
+
+impl SampleReader for TripleBufferReader {
+    fn channels(&self) -> usize {
+        self.channels
+    }
+
+    fn poll(&mut self) -> Option<&[f32]> {
+        if self.output.update() {
+            let samples = self.output.peek_output_buffer();
+            if !samples.is_empty() {
+                return Some(samples);
+            }
+        }
+        None
     }
 }
 
 /// Shared scope data source for lock-free polling from the widget.
 ///
-/// Wraps a `triple_buffer::Output<Vec<f32>>` so the Scope widget can
-/// poll for new audio data during animation frames without going
-/// through Xilem's view rebuild cycle.
+/// Wraps a [`SampleReader`], by default a `triple_buffer::Output<Vec<f32>>`,
+/// so the Scope widget can poll for new audio data during animation
+/// frames without going through Xilem's view rebuild cycle.
 ///
 /// Create one from the triple-buffer output that pairs with your DSP
-/// thread's input. Each `ScopeSource` gets a unique ID so the view
+/// thread's input, or from a [`RingConsumer`](crate::RingConsumer) for a
+/// gapless stream. Each `ScopeSource` gets a unique ID so the view
 /// layer can detect when the source is replaced (e.g. on audio device
 /// change). Cloning shares the same underlying buffer and ID.
+///
+/// A source feeds one display at a time: the display takes the
+/// underlying reader through [`reader`](Self::reader) and polls it
+/// without a lock or a copy. To show the same signal in several
+/// displays, give each its own source and have the DSP thread publish
+/// to all of them.
+///
+/// For several traces, write interleaved frames into the triple buffer
+/// and create the source with [`ScopeSource::interleaved`].
 #[derive(Clone)]
 pub struct ScopeSource {
-    inner: Arc<Mutex<triple_buffer::Output<Vec<f32>>>>,
+    shared: Arc<SourceShared>,
+    channels: usize,
     /// Unique ID for detecting source replacement.
     id: u64,
 }
 
-static SCOPE_SOURCE_NEXT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+/// State shared by all clones and the reader of one [`ScopeSource`].
+struct SourceShared {
+    /// The reader while no display holds it
+    slot: Mutex<Option<Box<dyn SampleReader>>>,
+    /// Set while a display holds the reader, so others need not lock `slot`
+    claimed: AtomicBool,
+}
+
+impl SourceShared {
+    /// Take the reader if no display holds it.
+    fn claim(&self) -> Option<Box<dyn SampleReader>> {
+        if self.claimed.swap(true, Ordering::Acquire) {
+            return None;
+        }
+        let reader = self.slot.lock().ok().and_then(|mut slot| slot.take());
+        if reader.is_none() {
+            self.claimed.store(false, Ordering::Release);
+        }
+        reader
+    }
+
+    /// Put the reader back for the next display.
+    fn release(&self, reader: Box<dyn SampleReader>) {
+        if let Ok(mut slot) = self.slot.lock() {
+            *slot = Some(reader);
+        }
+        self.claimed.store(false, Ordering::Release);
+    }
+}
+
+/// Returned by [`ScopeSource::reader`] while another display reads the
+/// source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SourceInUse;
+
+impl fmt::Display for SourceInUse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("scope source is already read by another display")
+    }
+}
+
+impl std::error::Error for SourceInUse {}
+
+static SCOPE_SOURCE_NEXT_ID: AtomicU64 = AtomicU64::new(1);
 
 impl ScopeSource {
     pub fn new(output: triple_buffer::Output<Vec<f32>>) -> Self {
+        Self::interleaved(output, 1)
+    }
+
+    /// A source whose buffers hold interleaved frames of `channels` channels.
+    pub fn interleaved(output: triple_buffer::Output<Vec<f32>>, channels: usize) -> Self {
+        Self::from_reader(TripleBufferReader { output, channels: channels.max(1) })
+    }
+
+    /// A source reading from any [`SampleReader`], such as a
+    /// [`RingConsumer`](crate::RingConsumer).
+    pub fn from_reader(reader: impl SampleReader + 'static) -> Self {
+        let channels = reader.channels().max(1);
+        let reader: Box<dyn SampleReader> = Box::new(reader);
         Self {
-            inner: Arc::new(Mutex::new(output)),
-            id: SCOPE_SOURCE_NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+            channels,
+            shared: Arc::new(SourceShared {
+                slot: Mutex::new(Some(reader)),
+                claimed: AtomicBool::new(false),
+            }),
+            id: SCOPE_SOURCE_NEXT_ID.fetch_add(1, Ordering::Relaxed),
         }
     }
 
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
     pub fn id(&self) -> u64 {
         self.id
     }
 
-    /// Poll for new data. Returns Some if the buffer has been updated.
+    /// Take the source's reader for one display. Fails while another
+    /// display holds it; dropping that display's reader frees the source.
+    pub fn reader(&self) -> Result<ScopeReader, SourceInUse> {
+        let reader = self.shared.claim().ok_or(SourceInUse)?;
+        Ok(ScopeReader {
+            shared: Arc::clone(&self.shared),
+            channels: self.channels,
+            reader: Some(reader),
+        })
+    }
+
+    /// Poll for new data, copying it into a fresh buffer.
+    ///
+    /// Always returns None while a display (or any other
+    /// [`ScopeReader`]) holds the source, so it can't be combined with
+    /// passing the source to a view.
+    #[deprecated(
+        note = "use `ScopeSource::reader` and poll the `ScopeReader`; \
+                this returns None while a display holds the source"
+    )]
     pub fn poll(&self) -> Option<ScopeBuffer> {
-        let mut out = self.inner.lock().unwrap();
-        if out.update() {
-            let samples = out.peek_output_buffer();
-            if !samples.is_empty() {
-                return Some(ScopeBuffer::from_arc(Arc::new(samples.clone())));
+        let mut reader = self.shared.claim()?;
+        let buffer = reader
+            .poll()
+            .map(|samples| ScopeBuffer::interleaved(samples.to_vec(), self.channels));
+        self.shared.release(reader);
+        buffer
+    }
+}
+
+/// Claim `source`'s reader for a display widget, logging once (tracked
+/// by `warned`) while another display holds it.
+pub(crate) fn claim_reader(source: &ScopeSource, warned: &mut bool) -> Option<ScopeReader> {
+    match source.reader() {
+        Ok(reader) => {
+            *warned = false;
+            Some(reader)
+        }
+        Err(err) => {
+            if !*warned {
+                *warned = true;
+                tracing::warn!(source = source.id(), "{err}; display stays blank until it is free");
             }
+            None
         }
-        None
     }
 }
 
-/// Zero-crossing detection mode
-#[derive(Clone, Copy, Debug)]
+/// Reading end of a [`ScopeSource`], held by one display.
+///
+/// Polls the underlying [`SampleReader`] directly: no lock, and for the
+/// triple buffer no allocation and no copy. Dropping it hands the
+/// reader back to the source.
+pub struct ScopeReader {
+    shared: Arc<SourceShared>,
+    channels: usize,
+    /// Only `None` while being dropped
+    reader: Option<Box<dyn SampleReader>>,
+}
+
+impl SampleReader for ScopeReader {
+    fn channels(&self) -> usize {
+        self.channels
+    }
+
+    fn poll(&mut self) -> Option<&[f32]> {
+        self.reader.as_mut()?.poll()
+    }
+
+    fn is_gapless(&self) -> bool {
+        self.reader.as_ref().is_some_and(|reader| reader.is_gapless())
+    }
+}
+
+impl Drop for ScopeReader {
+    fn drop(&mut self) {
+        if let Some(reader) = self.reader.take() {
+            self.shared.release(reader);
+        }
+    }
+}
+
+/// Which crossing of the trigger level starts a sweep.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum TriggerEdge {
+    /// Trigger when the signal rises through the level
+    #[default]
+    Rising,
+    /// Trigger when the signal falls through the level
+    Falling,
+}
+
+/// How several channels share the display.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum TraceLayout {
+    /// All traces drawn over each other on the full display.
+    #[default]
+    Overlay,
+    /// Each trace in its own horizontal lane, top to bottom.
+    Stacked,
+}
+
+/// When the display updates, as on a hardware oscilloscope.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
 pub enum TriggerMode {
-    /// Trigger on negative-to-positive crossing
-    RisingEdge,
-    /// Trigger on positive-to-negative crossing
-    #[allow(dead_code)]
-    FallingEdge,
+    /// Update on every trigger; free-run when no trigger has been seen
+    /// for a while, so a flat or silent signal is still visible.
+    #[default]
+    Auto,
+    /// Update only on a trigger; otherwise keep the last captured trace.
+    Normal,
+    /// Capture one triggered trace, then stop. Click the display to re-arm.
+    Single,
+    /// No trigger: new samples enter at the right edge and the trace
+    /// scrolls left like a chart recorder. For slow signals such as LFOs,
+    /// envelopes and control voltages; set a long timebase and feed a
+    /// gapless source.
+    Roll,
+}
+
+/// What the scope plots.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum DisplayMode {
+    /// Signal against time, triggered.
+    #[default]
+    Waveform,
+    /// Lissajous figure: channel 0 horizontally, channel 1 vertically.
+    Xy,
+    /// Vectorscope: the XY plot rotated 45°, so mono is a vertical line
+    /// and out-of-phase content spreads sideways.
+    Goniometer,
 }
 
 /// An oscilloscope widget that displays audio waveforms.
 ///
-/// The zero-crossing trigger point is centered in the display: the left
-/// half shows the waveform before the crossing, the right half shows
-/// after. Changing frequency expands the waveform symmetrically from
-/// the center. The trigger search starts at `display_width / 2` into
-/// the raw buffer to ensure enough pre-trigger data for the left side.
+/// The trigger point is centered in the display: the left half shows
+/// the waveform before the crossing, the right half shows after.
+/// Changing frequency expands the waveform symmetrically from the
+/// center. The trigger search starts half a display window into the
+/// raw buffer to ensure enough pre-trigger data for the left side.
+///
+/// The trigger fires when the signal crosses the trigger level on the
+/// selected edge, with a hysteresis band against noise. After a trigger,
+/// further triggers are ignored for the holdoff time, measured in signal
+/// time at the configured sample rate. The level is shown as a dashed
+/// marker that can be dragged up and down.
+///
+/// Without a timebase the display shows one sample per pixel. With a
+/// timebase (milliseconds per division, at the configured sample rate)
+/// the window covers a fixed stretch of time whatever the sample rate:
+/// longer windows are drawn as an envelope, shorter ones interpolated,
+/// and the time divisions are labelled relative to the trigger point.
+///
+/// With persistence enabled, earlier traces stay on screen and fade out
+/// over the decay time, like the phosphor of an analog tube. Traces
+/// overlap translucently, so paths the signal takes often glow brighter
+/// than rare excursions: noisy or modulated signals read as a steady
+/// intensity-graded band instead of flickering.
+///
+/// Multi-channel buffers are drawn as one trace per channel, overlaid or
+/// stacked, each in its own color. The trigger is taken from one selected
+/// channel and the same window is shown for all, so the traces stay
+/// phase-aligned.
+///
+/// The display doubles as a measuring tool:
+///
+/// - Click to freeze the display (a pause sign shows); click again to
+///   resume. In Single mode a click after the capture re-arms instead.
+/// - Drag to zoom: right / left zooms time in / out around the trigger,
+///   up / down zooms amplitude. The mouse wheel zooms time, or amplitude
+///   with Shift. Double-click to reset the zoom.
+/// - Right-click to place up to two measurement cursors (a third click
+///   clears them) and drag them with the primary button. The readout
+///   shows the amplitude under each cursor on the trigger channel and,
+///   with both placed, Δt and 1/Δt.
+/// - Drag the trigger-level marker to change the level.
+///
+/// In the XY and goniometer modes the first two channels of stereo
+/// frames are plotted against each other (a mono source plots against
+/// itself). Recent frames fade out over the persistence time, and a
+/// phase-correlation meter along the bottom runs from -1 (out of phase)
+/// through 0 (unrelated) to +1 (mono). The trigger is not used.
 ///
 /// Updates independently of Xilem's rebuild cycle by polling a shared
 /// `ScopeSource` (triple-buffer output) during `on_anim_frame`.
 ///
 /// Features:
-/// - Centered zero-crossing trigger with hysteresis for stable display
+/// - Centered level trigger with hysteresis and holdoff for stable display
+/// - Auto, Normal and Single-shot trigger modes, plus untriggered roll
+/// - Multiple channels, overlaid or stacked, triggered from one channel
 /// - Accepts `ScopeSource` for lock-free polling from real-time DSP threads
-/// - Decimates data for display (CPU friendly)
+/// - Sample-rate-aware timebase with time-axis labels
+/// - Click to freeze, drag/wheel zoom and measurement cursors
+/// - Phosphor persistence with intensity grading
+/// - XY / goniometer display with phase-correlation meter
+/// - Min/max envelope when zoomed out, so transients never vanish,
+///   with an optional RMS band
 /// - ~60fps rendering via animation frames
-/// - Fixed 192x196 pixel display area
+/// - Fills the space its parent offers (192x196 when unconstrained)
 pub struct Scope {
-    /// The display buffer (decimated for rendering)
-    display_points: Vec<f32>,
-    /// Raw buffer for trigger detection
-    raw_buffer: Vec<f32>,
-    /// Number of display points to show
+    /// The display columns, one buffer per channel
+    display_points: Vec<Vec<Column>>,
+    /// More than one sample per column: draw an envelope instead of a line
+    envelope: bool,
+    /// Draw the RMS band inside the envelope
+    show_rms: bool,
+    /// Raw buffers for trigger detection, one per channel; a deque so
+    /// old samples drop off the front without moving the rest
+    raw_buffer: Vec<VecDeque<f32>>,
+    /// Number of display points to show, one per pixel column
     display_width: usize,
-    /// Trigger mode
+    /// Raw-buffer index of the trigger point currently on display
+    shown_trigger: Option<usize>,
+    trigger_edge: TriggerEdge,
     trigger_mode: TriggerMode,
-    /// Hysteresis threshold for zero-crossing (prevents jitter)
+    /// Signal value the trigger fires at
+    trigger_level: f32,
+    /// Hysteresis band either side of the level (prevents jitter)
     trigger_threshold: f32,
-    /// Waveform color
-    wave_color: Color,
+    holdoff: Duration,
+    sample_rate: f64,
+    /// Timebase in milliseconds per division; `None` shows one sample per pixel
+    ms_per_div: Option<f64>,
+    /// Division labels, rebuilt in layout when the timebase changes
+    time_labels: Vec<Layout<BrushIndex>>,
+    labels_dirty: bool,
+    /// Total samples ingested, for placing triggers in signal time
+    samples_seen: u64,
+    /// Absolute sample index of the last trigger
+    last_trigger: Option<u64>,
+    /// Single mode has captured its trace and waits to be re-armed
+    stopped: bool,
+    /// Pointer gesture in progress
+    drag: Option<Drag>,
+    /// Display held by a click; new data is ignored
+    frozen: bool,
+    /// Magnification of the time and amplitude axes (1 = none)
+    time_zoom: f64,
+    amp_zoom: f64,
+    /// Measurement cursors, as fractions of the display width
+    cursors: [Option<f64>; 2],
+    /// Cursor readout lines, rebuilt in layout
+    cursor_labels: Vec<Layout<BrushIndex>>,
+    cursors_dirty: bool,
+    /// Channel the trigger watches
+    trigger_channel: usize,
+    trace_layout: TraceLayout,
+    /// Waveform colors by channel, cycled for further channels
+    trace_colors: Vec<Color>,
     /// Background color
     bg_color: Color,
     /// Grid color
     grid_color: Color,
     /// Generation counter to detect new data
     generation: u64,
+    /// Decay time of earlier traces; `None` shows only the latest
+    persistence: Option<Duration>,
+    /// Earlier traces, oldest first
+    afterglow: VecDeque<Afterglow>,
+    display_mode: DisplayMode,
+    /// Recent XY frames as (x, y) points with their age in seconds, oldest first
+    xy_frames: VecDeque<(Vec<(f32, f32)>, f32)>,
+    xy_persistence: Duration,
+    /// Smoothed phase correlation of the XY channels, -1..1
+    correlation: f32,
     /// Optional shared source for polling new data during anim frames
     source: Option<ScopeSource>,
+    /// The source's output, claimed on the first anim frame
+    reader: Option<ScopeReader>,
+    /// Another display held the source on the last try (already logged)
+    source_busy: bool,
 }
 
 impl Scope {
     pub fn new() -> Self {
         let display_w = (SCOPE_WIDTH - PADDING * 2.0) as usize;
         Self {
-            display_points: vec![0.0; display_w],
-            raw_buffer: Vec::new(),
+            display_points: vec![vec![Column::default(); display_w]],
+            envelope: false,
+            show_rms: false,
+            raw_buffer: vec![VecDeque::new()],
             display_width: display_w,
-            trigger_mode: TriggerMode::RisingEdge,
+            shown_trigger: None,
+            trigger_edge: TriggerEdge::Rising,
+            trigger_mode: TriggerMode::Auto,
+            trigger_level: 0.0,
             trigger_threshold: 0.02,
-            wave_color: Color::from_rgb8(0x00, 0xFF, 0x80),
+            holdoff: Duration::ZERO,
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            ms_per_div: None,
+            time_labels: Vec::new(),
+            labels_dirty: false,
+            samples_seen: 0,
+            last_trigger: None,
+            stopped: false,
+            drag: None,
+            frozen: false,
+            time_zoom: 1.0,
+            amp_zoom: 1.0,
+            cursors: [None; 2],
+            cursor_labels: Vec::new(),
+            cursors_dirty: false,
+            trigger_channel: 0,
+            trace_layout: TraceLayout::Overlay,
+            trace_colors: DEFAULT_TRACE_COLORS.to_vec(),
             bg_color: Color::from_rgb8(0x0A, 0x0A, 0x0A),
             grid_color: Color::from_rgb8(0x20, 0x30, 0x20),
             generation: 0,
+            persistence: None,
+            afterglow: VecDeque::new(),
+            display_mode: DisplayMode::Waveform,
+            xy_frames: VecDeque::new(),
+            xy_persistence: DEFAULT_XY_PERSISTENCE,
+            correlation: 0.0,
             source: None,
+            reader: None,
+            source_busy: false,
         }
     }
 
@@ -166,120 +604,883 @@ impl Scope {
     }
 
     pub fn set_source(this: &mut WidgetMut<'_, Self>, source: ScopeSource) {
+        // Hand the old output back before claiming the new one
+        this.widget.reader = None;
+        this.widget.source_busy = false;
         this.widget.source = Some(source);
         this.ctx.request_anim_frame();
     }
 
+    /// Set the color of the first (or only) trace.
     pub fn with_wave_color(mut self, color: Color) -> Self {
-        self.wave_color = color;
+        self.trace_colors[0] = color;
+        self
+    }
+
+    /// Set trace colors by channel, starting at channel 0. Channels
+    /// beyond the list reuse the colors cyclically.
+    pub fn with_trace_colors(mut self, colors: &[Color]) -> Self {
+        self.apply_trace_colors(colors);
+        self
+    }
+
+    pub fn with_trace_layout(mut self, layout: TraceLayout) -> Self {
+        self.trace_layout = layout;
         self
     }
 
+    /// Keep earlier traces on screen, fading out over `decay`.
+    pub fn with_persistence(mut self, decay: Option<Duration>) -> Self {
+        self.persistence = decay;
+        self
+    }
+
+    pub fn with_display_mode(mut self, mode: DisplayMode) -> Self {
+        self.display_mode = mode;
+        self
+    }
+
+    /// How long XY traces stay visible while fading out. Default 150 ms.
+    pub fn with_xy_persistence(mut self, persistence: Duration) -> Self {
+        self.xy_persistence = persistence;
+        self
+    }
+
+    /// Shade the RMS level inside the peak envelope, like a DAW
+    /// waveform view. Only visible when several samples share a column.
+    pub fn with_rms_band(mut self, show: bool) -> Self {
+        self.show_rms = show;
+        self
+    }
+
+    /// Select the channel the trigger watches. Default 0.
+    pub fn with_trigger_channel(mut self, channel: usize) -> Self {
+        self.trigger_channel = channel;
+        self
+    }
+
+    pub fn set_wave_color(this: &mut WidgetMut<'_, Self>, color: Color) {
+        this.widget.trace_colors[0] = color;
+        this.ctx.request_render();
+    }
+
+    pub fn set_trace_colors(this: &mut WidgetMut<'_, Self>, colors: &[Color]) {
+        this.widget.apply_trace_colors(colors);
+        this.ctx.request_render();
+    }
+
+    pub fn set_trace_layout(this: &mut WidgetMut<'_, Self>, layout: TraceLayout) {
+        this.widget.trace_layout = layout;
+        this.ctx.request_render();
+    }
+
+    pub fn set_persistence(this: &mut WidgetMut<'_, Self>, decay: Option<Duration>) {
+        this.widget.persistence = decay;
+        if decay.is_none() {
+            this.widget.afterglow.clear();
+        }
+        this.ctx.request_render();
+    }
+
+    pub fn set_display_mode(this: &mut WidgetMut<'_, Self>, mode: DisplayMode) {
+        this.widget.display_mode = mode;
+        this.widget.xy_frames.clear();
+        this.widget.afterglow.clear();
+        this.widget.drag = None;
+        this.ctx.request_render();
+    }
+
+    pub fn set_xy_persistence(this: &mut WidgetMut<'_, Self>, persistence: Duration) {
+        this.widget.xy_persistence = persistence;
+        this.ctx.request_render();
+    }
+
+    pub fn set_rms_band(this: &mut WidgetMut<'_, Self>, show: bool) {
+        this.widget.show_rms = show;
+        this.ctx.request_render();
+    }
+
+    pub fn set_trigger_channel(this: &mut WidgetMut<'_, Self>, channel: usize) {
+        this.widget.trigger_channel = channel;
+        this.ctx.request_render();
+    }
+
+    /// Set the hysteresis band either side of the trigger level.
     pub fn with_trigger_threshold(mut self, threshold: f32) -> Self {
         self.trigger_threshold = threshold;
         self
     }
 
+    pub fn with_trigger_edge(mut self, edge: TriggerEdge) -> Self {
+        self.trigger_edge = edge;
+        self
+    }
+
+    pub fn with_trigger_mode(mut self, mode: TriggerMode) -> Self {
+        self.trigger_mode = mode;
+        self
+    }
+
+    /// Set the signal value the trigger fires at (default 0, zero-crossing).
+    pub fn with_trigger_level(mut self, level: f32) -> Self {
+        self.trigger_level = level;
+        self
+    }
+
+    /// Ignore further triggers for `holdoff` after each trigger.
+    pub fn with_holdoff(mut self, holdoff: Duration) -> Self {
+        self.holdoff = holdoff;
+        self
+    }
+
+    /// Sample rate of the incoming data, used for time-based settings.
+    pub fn with_sample_rate(mut self, sample_rate: f64) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    pub fn set_trigger_threshold(this: &mut WidgetMut<'_, Self>, threshold: f32) {
+        this.widget.trigger_threshold = threshold;
+    }
+
+    pub fn set_trigger_edge(this: &mut WidgetMut<'_, Self>, edge: TriggerEdge) {
+        this.widget.trigger_edge = edge;
+    }
+
+    /// Change the trigger mode. Switching to `Single` arms a new capture.
+    pub fn set_trigger_mode(this: &mut WidgetMut<'_, Self>, mode: TriggerMode) {
+        if (mode == TriggerMode::Roll) != (this.widget.trigger_mode == TriggerMode::Roll) {
+            // Time labels change origin and earlier traces no longer fit
+            this.widget.labels_dirty = true;
+            this.widget.afterglow.clear();
+            this.ctx.request_layout();
+        }
+        this.widget.trigger_mode = mode;
+        this.widget.stopped = false;
+        this.ctx.request_render();
+    }
+
+    pub fn set_trigger_level(this: &mut WidgetMut<'_, Self>, level: f32) {
+        this.widget.trigger_level = level;
+        this.ctx.request_render();
+    }
+
+    pub fn set_holdoff(this: &mut WidgetMut<'_, Self>, holdoff: Duration) {
+        this.widget.holdoff = holdoff;
+    }
+
+    /// Show `ms` milliseconds per time division, labelling the divisions.
+    pub fn with_ms_per_div(mut self, ms: f64) -> Self {
+        self.ms_per_div = Some(ms);
+        self.labels_dirty = true;
+        self
+    }
+
+    pub fn set_sample_rate(this: &mut WidgetMut<'_, Self>, sample_rate: f64) {
+        this.widget.sample_rate = sample_rate;
+        this.ctx.request_render();
+    }
+
+    /// Set the timebase, or `None` for one sample per pixel.
+    pub fn set_ms_per_div(this: &mut WidgetMut<'_, Self>, ms: Option<f64>) {
+        this.widget.ms_per_div = ms;
+        this.widget.labels_dirty = true;
+        this.ctx.request_layout();
+        this.ctx.request_render();
+    }
+
+    /// Re-arm a stopped single-shot capture.
+    pub fn rearm(this: &mut WidgetMut<'_, Self>) {
+        this.widget.stopped = false;
+        this.ctx.request_render();
+    }
+
+    /// Hold the current display, ignoring new data, or resume.
+    pub fn set_frozen(this: &mut WidgetMut<'_, Self>, frozen: bool) {
+        this.widget.frozen = frozen;
+        this.ctx.request_render();
+    }
+
     /// Push a new buffer of samples. The scope will find a zero-crossing
     /// trigger point and decimate the data for display.
     pub fn push_buffer(this: &mut WidgetMut<'_, Self>, buffer: &ScopeBuffer) {
-        if this.widget.ingest_buffer(buffer) {
+        if this.widget.ingest(&buffer.samples, buffer.channels) {
+            if this.widget.has_cursors() {
+                this.widget.cursors_dirty = true;
+                this.ctx.request_layout();
+            }
             this.ctx.request_render();
         }
     }
 
-    /// Internal: ingest a buffer and return true if display was updated.
-    fn ingest_buffer(&mut self, buffer: &ScopeBuffer) -> bool {
-        let samples = &buffer.samples;
-        if samples.is_empty() {
-            return false;
+    fn apply_trace_colors(&mut self, colors: &[Color]) {
+        if self.trace_colors.len() < colors.len() {
+            self.trace_colors.resize(colors.len(), DEFAULT_TRACE_COLORS[0]);
         }
+        self.trace_colors[..colors.len()].copy_from_slice(colors);
+    }
 
-        // Append to raw buffer, keep a reasonable amount for trigger search
-        let max_raw = self.display_width * 4;
-        self.raw_buffer.extend_from_slice(samples);
-        if self.raw_buffer.len() > max_raw {
-            let drain = self.raw_buffer.len() - max_raw;
-            self.raw_buffer.drain(..drain);
-        }
+    fn trace_color(&self, channel: usize) -> Color {
+        self.trace_colors[channel % self.trace_colors.len()]
+    }
 
-        // Find trigger point (zero-crossing with hysteresis)
-        let trigger_pos = self.find_trigger_point();
+    /// Number of channels in the data currently shown.
+    fn channels(&self) -> usize {
+        self.raw_buffer.len()
+    }
 
-        // Center the trigger point in the display: show half before, half after.
-        let half = self.display_width / 2;
-        let display_start = trigger_pos.saturating_sub(half);
-        let display_end = (trigger_pos + half).min(self.raw_buffer.len());
-        let span = display_end - display_start;
+    /// The trigger channel, falling back to the first if out of range.
+    fn trigger_channel(&self) -> usize {
+        if self.trigger_channel < self.channels() { self.trigger_channel } else { 0 }
+    }
+
+    /// Internal: ingest interleaved samples and return true if display was updated.
+    fn ingest(&mut self, samples: &[f32], channels: usize) -> bool {
+        let channels = channels.max(1);
+        let frames = samples.len() / channels;
+        if frames == 0 || self.frozen {
+            return false;
+        }
+        if self.display_mode != DisplayMode::Waveform {
+            self.ingest_xy(samples, channels);
+            return true;
+        }
+        if self.stopped {
+            return false;
+        }
+        if channels != self.channels() {
+            // Channel layout changed: start over
+            self.raw_buffer = vec![VecDeque::new(); channels];
+            self.shown_trigger = None;
+            self.afterglow.clear();
+            self.display_points = vec![vec![Column::default(); self.display_width]; channels];
+        }
+        self.samples_seen += frames as u64;
 
-        // Copy exactly the centered span into display buffer (1:1 or decimated)
-        if span >= self.display_width {
-            // Decimate: map display_width points from the span
-            let step = span as f64 / self.display_width as f64;
-            for i in 0..self.display_width {
-                let src_idx = display_start + (i as f64 * step) as usize;
-                self.display_points[i] = self.raw_buffer[src_idx.min(self.raw_buffer.len() - 1)];
+        // Append to raw buffers, keep a reasonable amount for trigger search:
+        // a few windows' worth, so long windows still find a trigger.
+        let max_raw = (self.window_samples() * 4)
+            .min(self.seconds_to_samples(MAX_RAW_SECS) as usize)
+            .max(self.display_width * 4);
+        for frame in samples.chunks_exact(channels) {
+            for (raw, &sample) in self.raw_buffer.iter_mut().zip(frame) {
+                raw.push_back(sample);
             }
+        }
+        let drain = self.raw_buffer[0].len().saturating_sub(max_raw);
+        if drain > 0 {
+            for raw in &mut self.raw_buffer {
+                raw.drain(..drain);
+            }
+            self.shown_trigger = self.shown_trigger.and_then(|pos| pos.checked_sub(drain));
+        }
+        let raw_len = self.raw_buffer[0].len();
+
+        let roll = self.trigger_mode == TriggerMode::Roll;
+        let trigger_pos = if roll {
+            // Newest sample at the right edge
+            (raw_len as f64 - self.shown_samples() / 2.0).max(0.0) as usize
         } else {
-            // Not enough data: center what we have
-            let offset = (self.display_width - span) / 2;
-            for i in 0..self.display_width {
-                if i >= offset && i < offset + span {
-                    self.display_points[i] = self.raw_buffer[display_start + i - offset];
-                } else {
-                    self.display_points[i] = 0.0;
+            // Find trigger point (level crossing with hysteresis)
+            match self.find_trigger_point() {
+                Some(pos) => {
+                    self.last_trigger = Some(self.absolute_index(pos));
+                    self.stopped = self.trigger_mode == TriggerMode::Single;
+                    pos
                 }
+                None if self.trigger_mode == TriggerMode::Auto && self.auto_timed_out() => {
+                    // Free-run: center of available data
+                    let half = self.window_samples() / 2;
+                    half.min(raw_len.saturating_sub(1))
+                }
+                None => return false,
             }
+        };
+        // A rolling trace only shifts, so there is nothing to fade
+        if self.persistence.is_some() && self.generation > 0 && !roll {
+            self.keep_afterglow();
         }
-
+        self.shown_trigger = Some(trigger_pos);
+        self.render_columns(trigger_pos);
         self.generation += 1;
         true
     }
 
-    /// Find a stable zero-crossing trigger point using hysteresis.
+    /// Keep the trace on display as the newest afterglow before it is
+    /// replaced.
+    fn keep_afterglow(&mut self) {
+        let decay = self.persistence.map_or(0.0, |d| d.as_secs_f32());
+        let expired = self.afterglow.len() >= MAX_AFTERGLOW
+            || self.afterglow.front().is_some_and(|g| g.age >= decay);
+        // Reuse the oldest trace's storage when it has had its time
+        let mut glow = if expired {
+            self.afterglow.pop_front().unwrap_or_default()
+        } else {
+            Afterglow::default()
+        };
+        glow.columns.clone_from(&self.display_points);
+        glow.envelope = self.envelope;
+        glow.age = 0.0;
+        self.afterglow.push_back(glow);
+    }
+
+    /// Age the afterglow by `dt` seconds and drop faded traces.
+    /// Returns true if anything is left to fade.
+    fn age_afterglow(&mut self, dt: f32) -> bool {
+        let decay = self.persistence.map_or(0.0, |d| d.as_secs_f32());
+        for glow in &mut self.afterglow {
+            glow.age += dt;
+        }
+        while self.afterglow.front().is_some_and(|g| g.age > decay) {
+            self.afterglow.pop_front();
+        }
+        !self.afterglow.is_empty()
+    }
+
+    /// Fill the display columns from the raw buffers around `trigger_pos`.
+    fn render_columns(&mut self, trigger_pos: usize) {
+        // Center the trigger point in the display: show half a window before,
+        // half after. Every channel shows the same window so traces stay aligned.
+        let window = self.shown_samples();
+        let mut start = trigger_pos as f64 - window / 2.0;
+        let step = window / self.display_width as f64;
+        self.envelope = step > 1.0;
+        if self.envelope && self.trigger_mode == TriggerMode::Roll {
+            // Keep column boundaries on fixed sample positions, so the
+            // envelope scrolls instead of shimmering
+            let origin = self.absolute_index(0) as f64;
+            start = ((origin + start) / step).floor() * step - origin;
+        }
+        for (raw, display) in self.raw_buffer.iter().zip(&mut self.display_points) {
+            for (i, column) in display.iter_mut().enumerate() {
+                let pos = start + i as f64 * step;
+                *column = if self.envelope {
+                    // Several samples per column: keep their extremes
+                    Column::over(raw, pos, pos + step)
+                } else {
+                    // Interpolated; silence where data is missing
+                    Column::point(sample_at(raw, pos))
+                };
+            }
+        }
+    }
+
+    /// Match the display buffers to the drawable width, redrawing the
+    /// current trace at the new resolution.
+    fn resize_display(&mut self, width: f64) {
+        let display_w = ((width - PADDING * 2.0).floor() as usize).max(1);
+        if display_w == self.display_width {
+            return;
+        }
+        self.display_width = display_w;
+        self.display_points = vec![vec![Column::default(); display_w]; self.channels()];
+        self.afterglow.clear();
+        if let Some(pos) = self.shown_trigger {
+            self.render_columns(pos);
+        }
+    }
+
+    /// Add a buffer's stereo frames as the newest XY frame and update
+    /// the correlation reading.
+    fn ingest_xy(&mut self, samples: &[f32], channels: usize) {
+        // Second channel, or the first again for mono
+        let right = usize::from(channels > 1);
+        let frames = samples.len() / channels;
+        let stride = frames.div_ceil(MAX_XY_POINTS).max(1);
+
+        let (mut sum_lr, mut sum_ll, mut sum_rr) = (0.0_f32, 0.0_f32, 0.0_f32);
+        for frame in samples.chunks_exact(channels) {
+            let (l, r) = (frame[0], frame[right]);
+            sum_lr += l * r;
+            sum_ll += l * l;
+            sum_rr += r * r;
+        }
+        let denom = (sum_ll * sum_rr).sqrt();
+        if denom > f32::EPSILON {
+            let r = (sum_lr / denom).clamp(-1.0, 1.0);
+            self.correlation = CORRELATION_SMOOTHING * self.correlation + (1.0 - CORRELATION_SMOOTHING) * r;
+        }
+
+        // Reuse the storage of a frame that is about to expire
+        let mut points = match self.xy_frames.front() {
+            Some((_, age)) if *age >= self.xy_persistence.as_secs_f32() => {
+                self.xy_frames.pop_front().map(|(p, _)| p).unwrap_or_default()
+            }
+            _ => Vec::with_capacity(MAX_XY_POINTS),
+        };
+        points.clear();
+        points.extend(
+            samples.chunks_exact(channels).step_by(stride).map(|f| (f[0], f[right])),
+        );
+        self.xy_frames.push_back((points, 0.0));
+        self.generation += 1;
+    }
+
+    /// Age the XY frames by `dt` seconds and drop those past the
+    /// persistence time. Returns true if anything is left to fade.
+    fn age_xy_frames(&mut self, dt: f32) -> bool {
+        let persistence = self.xy_persistence.as_secs_f32();
+        for (_, age) in &mut self.xy_frames {
+            *age += dt;
+        }
+        // Always keep the newest frame so the display never goes blank
+        while self.xy_frames.len() > 1
+            && self.xy_frames.front().is_some_and(|(_, age)| *age > persistence)
+        {
+            self.xy_frames.pop_front();
+        }
+        self.xy_frames.len() > 1
+    }
+
+    /// Absolute sample index of a position in the raw buffer.
+    fn absolute_index(&self, pos: usize) -> u64 {
+        self.samples_seen - self.raw_buffer[0].len() as u64 + pos as u64
+    }
+
+    fn seconds_to_samples(&self, secs: f64) -> u64 {
+        (secs * self.sample_rate).max(0.0) as u64
+    }
+
+    /// Number of samples spanned by the display.
+    fn window_samples(&self) -> usize {
+        match self.ms_per_div {
+            Some(ms) => {
+                let secs = ms / 1000.0 * TIME_DIVISIONS as f64;
+                (self.seconds_to_samples(secs) as usize).max(2)
+            }
+            None => self.display_width,
+        }
+    }
+
+    /// Number of samples across the display after zooming.
+    fn shown_samples(&self) -> f64 {
+        self.window_samples() as f64 / self.time_zoom
+    }
+
+    /// Zoom by the given factors, keeping the trigger point centred.
+    fn zoom(&mut self, time: f64, amp: f64) {
+        self.time_zoom = (self.time_zoom * time).clamp(1.0, MAX_TIME_ZOOM);
+        self.amp_zoom = (self.amp_zoom * amp).clamp(1.0, MAX_AMP_ZOOM);
+        self.rezoomed();
+    }
+
+    /// Redraw at the current zoom: earlier traces no longer line up,
+    /// and labels and readouts change scale.
+    fn rezoomed(&mut self) {
+        self.afterglow.clear();
+        if let Some(pos) = self.shown_trigger {
+            self.render_columns(pos);
+        }
+        self.labels_dirty = true;
+        self.cursors_dirty = true;
+    }
+
+    fn has_cursors(&self) -> bool {
+        self.cursors.iter().any(Option::is_some)
+    }
+
+    /// Index of the cursor within grabbing distance of `x`, if any.
+    fn cursor_at(&self, x: f64, size: Size) -> Option<usize> {
+        let draw_w = size.width - PADDING * 2.0;
+        self.cursors.iter().position(|c| {
+            c.is_some_and(|frac| (PADDING + frac * draw_w - x).abs() <= MARKER_GRAB)
+        })
+    }
+
+    /// Right-click: place the next cursor, or clear both once placed.
+    fn place_cursor(&mut self, frac: f64) {
+        match self.cursors {
+            [None, _] => self.cursors[0] = Some(frac),
+            [Some(_), None] => self.cursors[1] = Some(frac),
+            [Some(_), Some(_)] => self.cursors = [None; 2],
+        }
+        self.cursors_dirty = true;
+    }
+
+    /// Rebuild the cursor readout: amplitude under each cursor and,
+    /// with both placed, the time between them and its inverse.
+    fn rebuild_cursor_labels(&mut self, ctx: &mut LayoutCtx<'_>) {
+        if !self.cursors_dirty {
+            return;
+        }
+        self.cursors_dirty = false;
+        self.cursor_labels.clear();
+        let Some(columns) = self.display_points.get(self.trigger_channel()) else {
+            return;
+        };
+        let mut lines: SmallVec<[String; 3]> = SmallVec::new();
+        for (i, cursor) in self.cursors.iter().enumerate() {
+            if let Some(frac) = cursor {
+                let last = columns.len().saturating_sub(1);
+                let idx = ((frac * columns.len() as f64) as usize).min(last);
+                let value = columns.get(idx).map_or(0.0, |c| c.max);
+                lines.push(format!("{}: {value:+.3}", i + 1));
+            }
+        }
+        if let [Some(a), Some(b)] = self.cursors {
+            let dt_ms = (b - a).abs() * self.shown_samples() / self.sample_rate * 1000.0;
+            let freq = if dt_ms > 0.0 { format_freq(1000.0 / dt_ms) } else { "-".to_string() };
+            lines.push(format!("Δt {}  {freq}", format_time(dt_ms)));
+        }
+        for line in lines {
+            self.cursor_labels.push(layout_text(ctx.text_contexts(), &line));
+        }
+    }
+
+    /// Rebuild the division labels: time relative to the trigger point,
+    /// or to the newest sample when rolling.
+    fn rebuild_labels(&mut self, ctx: &mut LayoutCtx<'_>) {
+        if !self.labels_dirty {
+            return;
+        }
+        self.labels_dirty = false;
+        self.time_labels.clear();
+        let Some(ms) = self.ms_per_div else {
+            return;
+        };
+        let ms = ms / self.time_zoom;
+        let origin = if self.trigger_mode == TriggerMode::Roll {
+            TIME_DIVISIONS as f64
+        } else {
+            TIME_DIVISIONS as f64 / 2.0
+        };
+        for div in 0..=TIME_DIVISIONS {
+            let t_ms = (div as f64 - origin) * ms;
+            self.time_labels.push(layout_text(ctx.text_contexts(), &format_time(t_ms)));
+        }
+    }
+
+    /// Whether Auto mode has waited long enough without a trigger.
+    fn auto_timed_out(&self) -> bool {
+        match self.last_trigger {
+            Some(t) => self.samples_seen - t > self.seconds_to_samples(AUTO_TIMEOUT_SECS),
+            None => true,
+        }
+    }
+
+    /// Find a stable trigger point using hysteresis.
     ///
-    /// Looks for a region where samples go from below -threshold
-    /// to above +threshold (rising edge) or vice versa.
-    fn find_trigger_point(&self) -> usize {
+    /// Looks for a region where samples go from below `level - threshold`
+    /// to above `level + threshold` (rising edge) or vice versa, outside
+    /// the holdoff window of the previous trigger.
+    fn find_trigger_point(&self) -> Option<usize> {
         let threshold = self.trigger_threshold;
-        let samples = &self.raw_buffer;
-        let half = self.display_width / 2;
-        // Start searching from half-display into the buffer so there's
+        let level = self.trigger_level;
+        let samples = &self.raw_buffer[self.trigger_channel()];
+        let half = self.window_samples() / 2;
+        // Start searching from half a window into the buffer so there's
         // enough data before the trigger for the left side of the display.
-        let search_start = half;
+        let mut search_start = half;
+        if let Some(last) = self.last_trigger {
+            let earliest = last + self.seconds_to_samples(self.holdoff.as_secs_f64());
+            let base = self.absolute_index(0);
+            if earliest > base {
+                search_start = search_start.max((earliest - base) as usize);
+            }
+        }
         let search_end = samples.len().saturating_sub(half);
 
         if search_start >= search_end || search_end < 2 {
-            return half.min(samples.len().saturating_sub(1));
+            return None;
         }
 
-        match self.trigger_mode {
-            TriggerMode::RisingEdge => {
-                let mut armed = false;
-                for i in search_start..search_end {
-                    if samples[i] < -threshold {
-                        armed = true;
-                    }
-                    if armed && samples[i] > threshold {
-                        return i;
-                    }
+        let mut armed = false;
+        for i in search_start..search_end {
+            let (arm, fire) = match self.trigger_edge {
+                TriggerEdge::Rising => (samples[i] < level - threshold, samples[i] > level + threshold),
+                TriggerEdge::Falling => (samples[i] > level + threshold, samples[i] < level - threshold),
+            };
+            if arm {
+                armed = true;
+            }
+            if armed && fire {
+                return Some(i);
+            }
+        }
+        None
+    }
+
+    fn paint_waveform(&self, painter: &mut Painter<'_>, size: Size) {
+        let draw_x = PADDING;
+        let draw_y = PADDING;
+        let draw_w = size.width - PADDING * 2.0;
+        let draw_h = size.height - PADDING * 2.0;
+
+        // Grid lines
+        let grid_stroke = Stroke::new(0.5);
+        let lanes = self.lanes();
+        let lane_h = draw_h / lanes as f64;
+        for lane in 0..lanes {
+            let lane_y = draw_y + lane_h * lane as f64;
+            // Horizontal center and quarter lines
+            for frac in [0.25, 0.5, 0.75] {
+                let y = lane_y + lane_h * frac;
+                painter.stroke(
+                    Line::new(Point::new(draw_x, y), Point::new(draw_x + draw_w, y)),
+                    &grid_stroke, self.grid_color,
+                ).draw();
+            }
+            // Lane separator
+            if lane > 0 {
+                painter.stroke(
+                    Line::new(Point::new(draw_x, lane_y), Point::new(draw_x + draw_w, lane_y)),
+                    &Stroke::new(1.0), self.grid_color,
+                ).draw();
+            }
+        }
+        // Vertical division lines
+        for div in 1..TIME_DIVISIONS {
+            let x = draw_x + draw_w * div as f64 / TIME_DIVISIONS as f64;
+            painter.stroke(
+                Line::new(Point::new(x, draw_y), Point::new(x, draw_y + draw_h)),
+                &grid_stroke, self.grid_color,
+            ).draw();
+        }
+
+        // Time labels along the bottom, kept inside the display at the
+        // edges and left out when the scope is too small to fit them
+        let labels_width: f64 = self.time_labels.iter().map(|l| l.width() as f64).sum();
+        let labels = if labels_width < draw_w { &self.time_labels[..] } else { &[] };
+        for (div, label) in labels.iter().enumerate() {
+            let (w, h) = (label.width() as f64, label.height() as f64);
+            let line_x = draw_x + draw_w * div as f64 / TIME_DIVISIONS as f64;
+            let right = (draw_x + draw_w - w - 1.0).max(draw_x + 1.0);
+            let x = (line_x - w / 2.0).clamp(draw_x + 1.0, right);
+            let y = draw_y + draw_h - h - 1.0;
+            render_text(painter, Affine::translate((x, y)), label, &[LABEL_COLOR.into()], true);
+        }
+
+        // Phosphor afterglow, oldest first, fading with age
+        let decay = self.persistence.map_or(0.0, |d| d.as_secs_f32()).max(f32::EPSILON);
+        for glow in &self.afterglow {
+            let alpha = AFTERGLOW_ALPHA * (1.0 - glow.age / decay).clamp(0.0, 1.0).powi(2);
+            for (channel, columns) in glow.columns.iter().enumerate() {
+                self.paint_trace(painter, size, channel, columns, glow.envelope, alpha);
+            }
+        }
+
+        // Waveforms, one per channel
+        for (channel, columns) in self.display_points.iter().enumerate() {
+            self.paint_trace(painter, size, channel, columns, self.envelope, 1.0);
+        }
+
+        // Rolling ignores the trigger level, so leave out its marker
+        if self.trigger_mode != TriggerMode::Roll {
+            self.paint_level_marker(painter, size);
+        }
+
+        // Measurement cursors and their readout in the top-left corner
+        for frac in self.cursors.iter().flatten() {
+            let x = draw_x + frac * draw_w;
+            painter.stroke(
+                Line::new(Point::new(x, draw_y), Point::new(x, draw_y + draw_h)),
+                &Stroke::new(1.0).with_dashes(0.0, [2.0, 2.0]),
+                CURSOR_COLOR.with_alpha(0.8),
+            ).draw();
+        }
+        if !self.cursor_labels.is_empty() {
+            let width = self.cursor_labels.iter().map(|l| l.width() as f64).fold(0.0, f64::max);
+            let height: f64 = self.cursor_labels.iter().map(|l| l.height() as f64).sum();
+            let backing =
+                Rect::new(draw_x + 2.0, draw_y + 2.0, draw_x + width + 8.0, draw_y + height + 6.0);
+            painter.fill(backing, self.bg_color.with_alpha(0.8)).fill_rule(Fill::NonZero).draw();
+            let mut y = draw_y + 4.0;
+            for label in &self.cursor_labels {
+                let at = Affine::translate((draw_x + 5.0, y));
+                render_text(painter, at, label, &[CURSOR_COLOR.into()], true);
+                y += label.height() as f64;
+            }
+        }
+
+        if self.frozen {
+            self.paint_frozen(painter, size);
+        }
+    }
+
+    /// Trigger level marker: dashed line with a handle at the right edge.
+    fn paint_level_marker(&self, painter: &mut Painter<'_>, size: Size) {
+        let (draw_x, draw_w) = (PADDING, size.width - PADDING * 2.0);
+        let level_y = self.level_to_y(size);
+        let marker_color = if self.stopped { STOPPED_COLOR } else { TRIGGER_COLOR };
+        painter.stroke(
+            Line::new(Point::new(draw_x, level_y), Point::new(draw_x + draw_w, level_y)),
+            &Stroke::new(0.5).with_dashes(0.0, [3.0, 3.0]),
+            marker_color.with_alpha(0.6),
+        ).draw();
+        let mut handle = BezPath::new();
+        handle.move_to(Point::new(draw_x + draw_w, level_y - 4.0));
+        handle.line_to(Point::new(draw_x + draw_w - 6.0, level_y));
+        handle.line_to(Point::new(draw_x + draw_w, level_y + 4.0));
+        handle.close_path();
+        painter.fill(&handle, marker_color).fill_rule(Fill::NonZero).draw();
+    }
+
+    /// Pause sign in the top-right corner while the display is frozen.
+    fn paint_frozen(&self, painter: &mut Painter<'_>, size: Size) {
+        let x = size.width - PADDING - 14.0;
+        let y = PADDING + 4.0;
+        for dx in [0.0, 5.0] {
+            let bar = Rect::new(x + dx, y, x + dx + 3.0, y + 10.0);
+            painter.fill(bar, STOPPED_COLOR).fill_rule(Fill::NonZero).draw();
+        }
+    }
+
+    /// Draw one channel's trace from its display columns.
+    fn paint_trace(
+        &self,
+        painter: &mut Painter<'_>,
+        size: Size,
+        channel: usize,
+        columns: &[Column],
+        envelope: bool,
+        alpha: f32,
+    ) {
+        if columns.is_empty() {
+            return;
+        }
+        let draw_x = PADDING;
+        let draw_w = size.width - PADDING * 2.0;
+        let (mid_y, half_h) = self.trace_geometry(channel, size);
+        let step = draw_w / columns.len() as f64;
+        // Clamp samples to -1..1 range for display
+        let amp = self.amp_zoom as f32;
+        let to_y = |v: f32| mid_y - (v * amp).clamp(-1.0, 1.0) as f64 * half_h;
+        let color = self.trace_color(channel).multiply_alpha(alpha);
+
+        if !envelope {
+            let mut path = BezPath::new();
+            for (i, column) in columns.iter().enumerate() {
+                let p = Point::new(draw_x + i as f64 * step, to_y(column.max));
+                if i == 0 {
+                    path.move_to(p);
+                } else {
+                    path.line_to(p);
                 }
             }
-            TriggerMode::FallingEdge => {
-                let mut armed = false;
-                for i in search_start..search_end {
-                    if samples[i] > threshold {
-                        armed = true;
-                    }
-                    if armed && samples[i] < -threshold {
-                        return i;
-                    }
+            painter.stroke(&path, &Stroke::new(1.5).with_caps(Cap::Round), color).draw();
+            return;
+        }
+
+        // Peak envelope: max along the top, min back along the bottom.
+        // The outline keeps it at least a pixel thick on quiet signals.
+        let envelope = band_path(columns, draw_x, step, |c| (to_y(c.max), to_y(c.min)));
+        painter.fill(&envelope, color.multiply_alpha(0.5)).fill_rule(Fill::NonZero).draw();
+        painter.stroke(&envelope, &Stroke::new(1.0), color.multiply_alpha(0.5)).draw();
+
+        if self.show_rms {
+            // RMS band around zero, kept inside the envelope
+            let rms = band_path(columns, draw_x, step, |c| {
+                (to_y(c.rms.min(c.max)), to_y((-c.rms).max(c.min)))
+            });
+            painter.fill(&rms, color).fill_rule(Fill::NonZero).draw();
+        }
+    }
+
+    fn paint_xy(&self, painter: &mut Painter<'_>, size: Size) {
+        // Square plot centred in the space above the correlation meter
+        let meter_h = CORRELATION_HEIGHT + PADDING * 2.0;
+        let side = (size.width - PADDING * 2.0).min(size.height - meter_h - PADDING * 2.0).max(0.0);
+        let center = Point::new(size.width / 2.0, PADDING + (size.height - meter_h - PADDING * 2.0) / 2.0);
+        let half = side / 2.0 - TRACE_INSET;
+        let goniometer = self.display_mode == DisplayMode::Goniometer;
+
+        // Cross-hairs, plus the L and R axes in goniometer mode
+        let grid_stroke = Stroke::new(0.5);
+        let mut axes: SmallVec<[_; 4]> = SmallVec::new();
+        axes.push(((0.0, -1.0), (0.0, 1.0)));
+        axes.push(((-1.0, 0.0), (1.0, 0.0)));
+        if goniometer {
+            let d = std::f64::consts::FRAC_1_SQRT_2;
+            axes.push(((-d, -d), (d, d)));
+            axes.push(((-d, d), (d, -d)));
+        }
+        for ((x0, y0), (x1, y1)) in axes {
+            painter.stroke(
+                Line::new(
+                    Point::new(center.x + x0 * half, center.y - y0 * half),
+                    Point::new(center.x + x1 * half, center.y - y1 * half),
+                ),
+                &grid_stroke, self.grid_color,
+            ).draw();
+        }
+
+        // Traces, oldest first so the newest lands on top
+        let persistence = self.xy_persistence.as_secs_f32().max(f32::EPSILON);
+        let color = self.trace_color(0);
+        let newest = self.xy_frames.len().saturating_sub(1);
+        for (frame, (points, age)) in self.xy_frames.iter().enumerate() {
+            // The newest frame stays bright even when the signal pauses
+            let alpha = if frame == newest { 1.0 } else { (1.0 - age / persistence).clamp(0.1, 1.0) };
+            let mut path = BezPath::new();
+            for (i, &(l, r)) in points.iter().enumerate() {
+                let (x, y) = if goniometer {
+                    // Side horizontally, mid vertically
+                    let d = std::f32::consts::FRAC_1_SQRT_2;
+                    ((r - l) * d, (l + r) * d)
+                } else {
+                    (l, r)
+                };
+                let p = Point::new(
+                    center.x + x.clamp(-1.0, 1.0) as f64 * half,
+                    center.y - y.clamp(-1.0, 1.0) as f64 * half,
+                );
+                if i == 0 {
+                    path.move_to(p);
+                } else {
+                    path.line_to(p);
                 }
             }
+            painter.stroke(&path, &Stroke::new(1.0), color.with_alpha(alpha)).draw();
+        }
+
+        // Correlation meter: -1 on the left, +1 on the right
+        let meter = Rect::new(
+            PADDING * 2.0,
+            size.height - PADDING * 2.0 - CORRELATION_HEIGHT,
+            size.width - PADDING * 2.0,
+            size.height - PADDING * 2.0,
+        );
+        painter.fill(meter, Color::from_rgb8(0x18, 0x18, 0x18)).fill_rule(Fill::NonZero).draw();
+        let mid_x = meter.center().x;
+        let value_x = mid_x + self.correlation as f64 * meter.width() / 2.0;
+        let bar_color = if self.correlation < 0.0 { CORRELATION_NEGATIVE } else { CORRELATION_POSITIVE };
+        let bar = Rect::new(mid_x.min(value_x), meter.y0 + 1.0, mid_x.max(value_x), meter.y1 - 1.0);
+        painter.fill(bar, bar_color).fill_rule(Fill::NonZero).draw();
+        painter.stroke(
+            Line::new(Point::new(mid_x, meter.y0), Point::new(mid_x, meter.y1)),
+            &grid_stroke, Color::from_rgb8(0x80, 0x80, 0x80),
+        ).draw();
+
+        if self.frozen {
+            self.paint_frozen(painter, size);
         }
+    }
+
+    /// Number of lanes the display is split into.
+    fn lanes(&self) -> usize {
+        match self.trace_layout {
+            TraceLayout::Overlay => 1,
+            TraceLayout::Stacked => self.channels(),
+        }
+    }
 
-        // Fallback: center of available data
-        half.min(samples.len().saturating_sub(1))
+    /// Vertical extent of a channel's trace area: (center y, half height).
+    fn trace_geometry(&self, channel: usize, size: Size) -> (f64, f64) {
+        let lanes = self.lanes();
+        let lane = if lanes > 1 { channel } else { 0 };
+        let lane_h = (size.height - PADDING * 2.0) / lanes as f64;
+        let mid_y = PADDING + lane_h * (lane as f64 + 0.5);
+        (mid_y, lane_h / 2.0 - TRACE_INSET)
+    }
+
+    fn level_to_y(&self, size: Size) -> f64 {
+        let (mid_y, half_h) = self.trace_geometry(self.trigger_channel(), size);
+        mid_y - (self.trigger_level as f64 * self.amp_zoom).clamp(-1.0, 1.0) * half_h
+    }
+
+    fn y_to_level(&self, y: f64, size: Size) -> f32 {
+        let (mid_y, half_h) = self.trace_geometry(self.trigger_channel(), size);
+        ((mid_y - y) / half_h / self.amp_zoom).clamp(-1.0, 1.0) as f32
     }
 }
 
@@ -288,23 +1489,158 @@ impl Widget for Scope {
 
     fn on_pointer_event(
         &mut self,
-        _ctx: &mut EventCtx<'_>,
+        ctx: &mut EventCtx<'_>,
         _props: &mut PropertiesMut<'_>,
-        _event: &PointerEvent,
+        event: &PointerEvent,
     ) {
+        let waveform = self.display_mode == DisplayMode::Waveform;
+        match event {
+            PointerEvent::Down(PointerButtonEvent { button, state, .. }) => {
+                let pos = ctx.local_position(state.position);
+                let size = ctx.content_box_size();
+                if *button == Some(PointerButton::Secondary) {
+                    if waveform {
+                        let draw_w = size.width - PADDING * 2.0;
+                        self.place_cursor(((pos.x - PADDING) / draw_w).clamp(0.0, 1.0));
+                        ctx.request_layout();
+                        ctx.request_render();
+                    }
+                    return;
+                }
+                if waveform && state.count == 2 {
+                    // Double-click: reset the zoom. The two clicks toggle
+                    // the freeze back to where it was.
+                    self.time_zoom = 1.0;
+                    self.amp_zoom = 1.0;
+                    self.rezoomed();
+                    ctx.request_layout();
+                    ctx.request_render();
+                }
+                ctx.capture_pointer();
+                let on_level = self.trigger_mode != TriggerMode::Roll
+                    && (pos.y - self.level_to_y(size)).abs() <= MARKER_GRAB;
+                self.drag = Some(if waveform && on_level {
+                    Drag::Level
+                } else if let Some(i) = self.cursor_at(pos.x, size).filter(|_| waveform) {
+                    Drag::Cursor(i)
+                } else {
+                    Drag::Zoom { origin: pos, last: pos, zooming: false }
+                });
+            }
+            PointerEvent::Move(PointerUpdate { current, .. }) => {
+                if !ctx.is_active() {
+                    return;
+                }
+                let pos = ctx.local_position(current.position);
+                let size = ctx.content_box_size();
+                match &mut self.drag {
+                    Some(Drag::Level) => {
+                        self.trigger_level = self.y_to_level(pos.y, size);
+                        ctx.request_render();
+                    }
+                    Some(Drag::Cursor(i)) => {
+                        let draw_w = size.width - PADDING * 2.0;
+                        self.cursors[*i] = Some(((pos.x - PADDING) / draw_w).clamp(0.0, 1.0));
+                        self.cursors_dirty = true;
+                        ctx.request_layout();
+                        ctx.request_render();
+                    }
+                    Some(Drag::Zoom { origin, last, zooming }) => {
+                        if !*zooming && (pos - *origin).hypot() > CLICK_SLOP {
+                            *zooming = true;
+                        }
+                        if !*zooming || !waveform {
+                            return;
+                        }
+                        // Right and up zoom in
+                        let time = ((pos.x - last.x) / ZOOM_DRAG_PIXELS).exp();
+                        let amp = ((last.y - pos.y) / ZOOM_DRAG_PIXELS).exp();
+                        *last = pos;
+                        self.zoom(time, amp);
+                        ctx.request_layout();
+                        ctx.request_render();
+                    }
+                    None => {}
+                }
+            }
+            PointerEvent::Up(..) => {
+                if !ctx.is_active() {
+                    return;
+                }
+                ctx.release_pointer();
+                if let Some(Drag::Zoom { zooming: false, .. }) = self.drag {
+                    // A click: re-arm a finished single shot, else toggle freeze
+                    if self.stopped {
+                        self.stopped = false;
+                        self.frozen = false;
+                    } else {
+                        self.frozen = !self.frozen;
+                    }
+                    ctx.request_render();
+                }
+                self.drag = None;
+            }
+            PointerEvent::Scroll(PointerScrollEvent { delta, state, .. }) => {
+                if !waveform {
+                    return;
+                }
+                ctx.set_handled();
+                let notches = match delta {
+                    ScrollDelta::LineDelta(_, y) => *y as f64,
+                    ScrollDelta::PixelDelta(p) => p.y / SCROLL_PIXELS_PER_NOTCH,
+                    ScrollDelta::PageDelta(_, y) => *y as f64,
+                };
+                let factor = ZOOM_PER_NOTCH.powf(notches);
+                if state.modifiers.shift() {
+                    self.zoom(1.0, factor);
+                } else {
+                    self.zoom(factor, 1.0);
+                }
+                ctx.request_layout();
+                ctx.request_render();
+            }
+            _ => {}
+        }
+    }
+
+    fn accepts_pointer_interaction(&self) -> bool {
+        true
     }
 
     fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {}
 
     fn on_anim_frame(
-        &mut self, ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, _interval: u64,
+        &mut self, ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, interval: u64,
     ) {
-        if let Some(ref source) = self.source {
-            if let Some(buf) = source.poll() {
-                if self.ingest_buffer(&buf) {
-                    ctx.request_render();
+        if self.source.is_some() {
+            // Claim the output on first use; the samples are borrowed from
+            // the triple buffer while ingesting, so the reader is moved out
+            let mut reader = self.reader.take().or_else(|| {
+                self.source.as_ref().and_then(|s| claim_reader(s, &mut self.source_busy))
+            });
+            if let Some(r) = reader.as_mut() {
+                let channels = r.channels();
+                if let Some(samples) = r.poll() {
+                    if self.ingest(samples, channels) {
+                        // Cursor amplitudes follow the new trace
+                        if self.has_cursors() {
+                            self.cursors_dirty = true;
+                            ctx.request_layout();
+                        }
+                        ctx.request_render();
+                    }
                 }
             }
+            self.reader = reader;
+            // Older traces keep fading between buffers
+            let dt = interval as f32 * 1e-9;
+            let fading = match self.display_mode {
+                DisplayMode::Waveform => self.persistence.is_some() && self.age_afterglow(dt),
+                DisplayMode::Xy | DisplayMode::Goniometer => self.age_xy_frames(dt),
+            };
+            if fading {
+                ctx.request_render();
+            }
             ctx.request_anim_frame();
         }
     }
@@ -320,21 +1656,30 @@ impl Widget for Scope {
         _ctx: &mut MeasureCtx<'_>,
         _props: &PropertiesRef<'_>,
         axis: Axis,
-        _len_req: LenReq,
+        len_req: LenReq,
         _cross_length: Option<f64>,
     ) -> f64 {
-        match axis {
-            Axis::Horizontal => SCOPE_WIDTH,
-            Axis::Vertical => SCOPE_HEIGHT,
+        let (preferred, min) = match axis {
+            Axis::Horizontal => (SCOPE_WIDTH, MIN_WIDTH),
+            Axis::Vertical => (SCOPE_HEIGHT, MIN_HEIGHT),
+        };
+        match len_req {
+            LenReq::MinContent => min,
+            LenReq::MaxContent => preferred,
+            // Take all the space on offer
+            LenReq::FitContent(space) => space.max(min),
         }
     }
 
     fn layout(
         &mut self,
-        _ctx: &mut LayoutCtx<'_>,
+        ctx: &mut LayoutCtx<'_>,
         _props: &PropertiesRef<'_>,
-        _size: Size,
+        size: Size,
     ) {
+        self.resize_display(size.width);
+        self.rebuild_labels(ctx);
+        self.rebuild_cursor_labels(ctx);
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx<'_>, _props: &PropertiesRef<'_>, painter: &mut Painter<'_>) {
@@ -345,65 +1690,9 @@ impl Widget for Scope {
         // Background
         painter.fill(rr, self.bg_color).fill_rule(Fill::NonZero).draw();
 
-        let draw_x = PADDING;
-        let draw_y = PADDING;
-        let draw_w = size.width - PADDING * 2.0;
-        let draw_h = size.height - PADDING * 2.0;
-        let mid_y = draw_y + draw_h / 2.0;
-
-        // Grid lines
-        let grid_stroke = Stroke::new(0.5);
-        // Horizontal center line
-        painter.stroke(
-            Line::new(Point::new(draw_x, mid_y), Point::new(draw_x + draw_w, mid_y)),
-            &grid_stroke, self.grid_color,
-        ).draw();
-        // Quarter lines
-        for frac in [0.25, 0.75] {
-            let y = draw_y + draw_h * frac;
-            painter.stroke(
-                Line::new(Point::new(draw_x, y), Point::new(draw_x + draw_w, y)),
-                &grid_stroke, self.grid_color,
-            ).draw();
-        }
-        // Vertical center
-        let mid_x = draw_x + draw_w / 2.0;
-        painter.stroke(
-            Line::new(Point::new(mid_x, draw_y), Point::new(mid_x, draw_y + draw_h)),
-            &grid_stroke, self.grid_color,
-        ).draw();
-        // Vertical quarters
-        for frac in [0.25, 0.75] {
-            let x = draw_x + draw_w * frac;
-            painter.stroke(
-                Line::new(Point::new(x, draw_y), Point::new(x, draw_y + draw_h)),
-                &grid_stroke, self.grid_color,
-            ).draw();
-        }
-
-        // Waveform
-        if !self.display_points.is_empty() {
-            let mut path = BezPath::new();
-            let step = draw_w / self.display_points.len() as f64;
-
-            for (i, &sample) in self.display_points.iter().enumerate() {
-                let x = draw_x + i as f64 * step;
-                // Clamp sample to -1..1 range for display
-                let clamped = sample.clamp(-1.0, 1.0) as f64;
-                let y = mid_y - clamped * (draw_h / 2.0 - 2.0);
-
-                if i == 0 {
-                    path.move_to(Point::new(x, y));
-                } else {
-                    path.line_to(Point::new(x, y));
-                }
-            }
-
-            painter.stroke(
-                &path,
-                &Stroke::new(1.5).with_caps(Cap::Round),
-                self.wave_color,
-            ).draw();
+        match self.display_mode {
+            DisplayMode::Waveform => self.paint_waveform(painter, size),
+            DisplayMode::Xy | DisplayMode::Goniometer => self.paint_xy(painter, size),
         }
 
         // Border
@@ -431,3 +1720,119 @@ impl Widget for Scope {
         trace_span!("Scope", id = id.trace())
     }
 }
+
+/// Pointer gesture on the display.
+enum Drag {
+    /// Moving the trigger-level marker
+    Level,
+    /// Moving a measurement cursor
+    Cursor(usize),
+    /// Pressed on the display: a click unless the pointer travels,
+    /// then a zoom drag
+    Zoom { origin: Point, last: Point, zooming: bool },
+}
+
+/// An earlier trace kept for phosphor persistence.
+#[derive(Default)]
+struct Afterglow {
+    columns: Vec<Vec<Column>>,
+    envelope: bool,
+    /// Seconds since it was replaced
+    age: f32,
+}
+
+/// One display column of a trace: the extremes and RMS level of the
+/// samples it covers.
+#[derive(Clone, Copy, Default)]
+struct Column {
+    min: f32,
+    max: f32,
+    rms: f32,
+}
+
+impl Column {
+    fn point(v: f32) -> Self {
+        Self { min: v, max: v, rms: v.abs() }
+    }
+
+    /// Summarize the samples from fractional index `from` up to `to`,
+    /// or silence where the range holds no data.
+    fn over(raw: &VecDeque<f32>, from: f64, to: f64) -> Self {
+        if to <= 0.0 {
+            return Self::default();
+        }
+        let lo = from.max(0.0).floor() as usize;
+        let hi = (to.max(0.0).floor() as usize).max(lo + 1).min(raw.len());
+        if lo >= hi {
+            return Self::default();
+        }
+        let mut column = Self { min: f32::MAX, max: f32::MIN, rms: 0.0 };
+        let mut sum_sq = 0.0;
+        for &v in raw.range(lo..hi) {
+            column.min = column.min.min(v);
+            column.max = column.max.max(v);
+            sum_sq += v * v;
+        }
+        column.rms = (sum_sq / (hi - lo) as f32).sqrt();
+        column
+    }
+}
+
+/// Closed outline of a band over the columns: `edges` gives the top
+/// and bottom y of each column.
+fn band_path(columns: &[Column], x0: f64, step: f64, edges: impl Fn(&Column) -> (f64, f64)) -> BezPath {
+    let mut path = BezPath::new();
+    for (i, column) in columns.iter().enumerate() {
+        let p = Point::new(x0 + i as f64 * step, edges(column).0);
+        if i == 0 {
+            path.move_to(p);
+        } else {
+            path.line_to(p);
+        }
+    }
+    for (i, column) in columns.iter().enumerate().rev() {
+        path.line_to(Point::new(x0 + i as f64 * step, edges(column).1));
+    }
+    path.close_path();
+    path
+}
+
+/// Linearly interpolated sample at fractional index `pos`, or silence
+/// outside the buffer.
+fn sample_at(raw: &VecDeque<f32>, pos: f64) -> f32 {
+    if pos < 0.0 || raw.is_empty() {
+        return 0.0;
+    }
+    let i = pos.floor() as usize;
+    let frac = (pos - i as f64) as f32;
+    match (raw.get(i), raw.get(i + 1)) {
+        (Some(&a), Some(&b)) => a + (b - a) * frac,
+        (Some(&a), None) if frac == 0.0 => a,
+        _ => 0.0,
+    }
+}
+
+/// Format a frequency in Hz for the cursor readout.
+fn format_freq(hz: f64) -> String {
+    if hz >= 1000.0 {
+        format!("{:.2}kHz", hz / 1000.0)
+    } else {
+        format!("{hz:.1}Hz")
+    }
+}
+
+/// Format a time offset in milliseconds for a division label.
+fn format_time(ms: f64) -> String {
+    let abs = ms.abs();
+    if abs < 1e-9 {
+        "0".to_string()
+    } else if abs >= 1000.0 {
+        format!("{:.1}s", ms / 1000.0)
+    } else if abs >= 10.0 {
+        format!("{ms:.0}ms")
+    } else if abs >= 1.0 {
+        format!("{ms:.1}ms")
+    } else {
+        format!("{:.0}µs", ms * 1000.0)
+    }
+}
@@ -0,0 +1,474 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use xilem::masonry::accesskit::{Node, Role};
+use xilem::masonry::core::keyboard::Modifiers;
+use xilem::masonry::core::{
+    AccessCtx, BrushIndex, EventCtx, LayoutCtx, MeasureCtx, PaintCtx, PointerButtonEvent,
+    PointerEvent, PointerScrollEvent, PointerUpdate, PropertiesMut, PropertiesRef, RegisterCtx,
+    TextEvent, Update, UpdateCtx, Widget, WidgetId, WidgetMut, render_text,
+};
+use xilem::masonry::imaging::Painter;
+use xilem::masonry::kurbo::{Affine, Axis, Point, Size, Stroke};
+use xilem::masonry::layout::LenReq;
+use xilem::masonry::parley::{FontContext, Layout, LayoutContext};
+use xilem::masonry::peniko::Color;
+
+use smallvec::SmallVec;
+use tracing::trace_span;
+
+use crate::taper::Taper;
+use crate::theme::DEFAULT_TINT;
+use crate::widgets::control::{ControlValue, KEY_FRACTION};
+use crate::widgets::entry::EntryGesture;
+use crate::widgets::gesture::ControlAction;
+use crate::widgets::level_meter::Orientation;
+use crate::widgets::readout::{ValueFormatter, layout_text};
+use crate::widgets::track::{self, Track, DEFAULT_LENGTH, MIN_LENGTH, THICKNESS, TRACK_WIDTH};
+
+/// Default divisor applied to scrolling while the fine modifier is held.
+const DEFAULT_FINE_DIVISOR: f64 = 10.0;
+/// Space between tick labels and the grip's reach.
+const LABEL_GAP: f64 = 3.0;
+
+/// A tick mark on a [`Slider`]'s scale, optionally labelled.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SliderTick {
+    pub value: f64,
+    pub label: Option<String>,
+}
+
+impl SliderTick {
+    pub fn new(value: f64) -> Self {
+        Self { value, label: None }
+    }
+
+    pub fn labeled(value: f64, label: impl Into<String>) -> Self {
+        Self { value, label: Some(label.into()) }
+    }
+}
+
+/// A general-purpose slider with the same look as [`Fader`](crate::widgets::fader::Fader).
+///
+/// Unlike the fader, the range has no built-in unit: values are mapped
+/// through a [`Taper`], optionally quantized to a step, and the scale
+/// shows user-supplied ticks. Use it for mix percentages, send levels,
+/// ratios and the like.
+///
+/// By default the lit fill grows from the minimum end. With a fill
+/// origin set the slider is bipolar and the fill grows from that value
+/// instead, e.g. from 0 on a -100..100 balance control.
+///
+/// Dragging moves the grip with the pointer. When focused, arrow keys
+/// move by one step (or 1% of travel when unstepped), PageUp/PageDown by
+/// ten, Home/End jump to min/max and Delete/Backspace reset to the
+/// default. Scrolling moves by one increment per notch, a tenth of that
+/// with Shift held on unstepped sliders. Double-click resets.
+///
+/// Emits [`ControlAction`]s: value changes bracketed by gesture begin/end.
+pub struct Slider {
+    control: ControlValue,
+    /// Value the lit fill grows from; `None` fills from the minimum.
+    fill_from: Option<f64>,
+    ticks: Vec<SliderTick>,
+    /// Shaped tick labels, parallel to `ticks`.
+    tick_labels: Vec<Option<Layout<BrushIndex>>>,
+    labels_dirty: bool,
+    /// Room taken by the labels on the scale side of the track.
+    label_extent: f64,
+    tint: Color,
+    orientation: Orientation,
+    length: f64,
+    fine_modifier: Modifiers,
+    fine_divisor: f64,
+    drag_start: Option<Point>,
+    drag_start_norm: f64,
+}
+
+impl Slider {
+    pub fn new(min: f64, max: f64, value: f64, default: f64) -> Self {
+        Self {
+            control: ControlValue::new(min, max, value, default),
+            fill_from: None,
+            ticks: Vec::new(),
+            tick_labels: Vec::new(),
+            labels_dirty: false,
+            label_extent: 0.0,
+            tint: DEFAULT_TINT,
+            orientation: Orientation::Vertical,
+            length: DEFAULT_LENGTH,
+            fine_modifier: Modifiers::SHIFT,
+            fine_divisor: DEFAULT_FINE_DIVISOR,
+            drag_start: None,
+            drag_start_norm: 0.0,
+        }
+    }
+
+    pub fn with_step(mut self, step: f64) -> Self { self.control.step = step; self }
+    pub fn with_taper(mut self, taper: Taper) -> Self { self.control.taper = taper; self }
+    pub fn with_tint(mut self, color: Color) -> Self { self.tint = color; self }
+
+    /// Make the slider bipolar, with the lit fill growing from `value`.
+    pub fn with_fill_from(mut self, value: f64) -> Self {
+        self.fill_from = Some(value);
+        self
+    }
+
+    pub fn with_ticks(mut self, ticks: Vec<SliderTick>) -> Self {
+        self.ticks = ticks;
+        self.labels_dirty = true;
+        self
+    }
+
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set the preferred track length in pixels. Default 140.
+    pub fn with_length(mut self, length: f64) -> Self {
+        self.length = length.max(MIN_LENGTH);
+        self
+    }
+
+    /// Show the value, formatted by `formatter`, while hovered or dragged.
+    pub fn with_readout(mut self, formatter: ValueFormatter) -> Self {
+        let value = self.control.value;
+        self.control.readout.set_formatter(Some(formatter), value);
+        self
+    }
+
+    /// Set the gesture that opens inline text entry.
+    pub fn with_entry_gesture(mut self, gesture: EntryGesture) -> Self {
+        self.control.entry_gesture = gesture;
+        self
+    }
+
+    /// Set the modifier that enables fine scrolling. `Modifiers::empty()`
+    /// disables fine mode.
+    pub fn with_fine_modifier(mut self, modifier: Modifiers) -> Self {
+        self.fine_modifier = modifier;
+        self
+    }
+
+    /// Set how much finer scrolling becomes while the modifier is held.
+    pub fn with_fine_divisor(mut self, divisor: f64) -> Self {
+        self.fine_divisor = divisor.max(1.0);
+        self
+    }
+
+    pub fn set_value(this: &mut WidgetMut<'_, Self>, value: f64) {
+        let v = this.widget.control.clamp(value);
+        if (this.widget.control.value - v).abs() > f64::EPSILON {
+            this.widget.control.value = v;
+            this.ctx.request_render();
+            if this.widget.control.readout.invalidate() { this.ctx.request_layout(); }
+        }
+    }
+
+    pub fn set_range(this: &mut WidgetMut<'_, Self>, min: f64, max: f64) {
+        this.widget.control.min = min;
+        this.widget.control.max = max;
+        this.widget.control.value = this.widget.control.clamp(this.widget.control.value);
+        this.widget.control.default = this.widget.control.clamp(this.widget.control.default);
+        this.ctx.request_render();
+        if this.widget.control.readout.invalidate() { this.ctx.request_layout(); }
+    }
+
+    pub fn set_step(this: &mut WidgetMut<'_, Self>, step: f64) {
+        this.widget.control.step = step;
+    }
+
+    pub fn set_taper(this: &mut WidgetMut<'_, Self>, taper: Taper) {
+        this.widget.control.taper = taper;
+        this.ctx.request_render();
+    }
+
+    pub fn set_fill_from(this: &mut WidgetMut<'_, Self>, value: Option<f64>) {
+        this.widget.fill_from = value;
+        this.ctx.request_render();
+    }
+
+    pub fn set_ticks(this: &mut WidgetMut<'_, Self>, ticks: Vec<SliderTick>) {
+        this.widget.ticks = ticks;
+        this.widget.labels_dirty = true;
+        this.ctx.request_layout();
+    }
+
+    pub fn set_tint(this: &mut WidgetMut<'_, Self>, color: Color) {
+        this.widget.tint = color;
+        this.ctx.request_render();
+    }
+
+    pub fn set_orientation(this: &mut WidgetMut<'_, Self>, orientation: Orientation) {
+        this.widget.orientation = orientation;
+        this.widget.labels_dirty = true;
+        this.ctx.request_layout();
+    }
+
+    pub fn set_length(this: &mut WidgetMut<'_, Self>, length: f64) {
+        this.widget.length = length.max(MIN_LENGTH);
+        this.ctx.request_layout();
+    }
+
+    pub fn set_readout(this: &mut WidgetMut<'_, Self>, formatter: Option<ValueFormatter>) {
+        let value = this.widget.control.value;
+        if this.widget.control.readout.set_formatter(formatter, value) {
+            this.ctx.request_layout();
+        }
+    }
+
+    pub fn set_entry_gesture(this: &mut WidgetMut<'_, Self>, gesture: EntryGesture) {
+        this.widget.control.entry_gesture = gesture;
+        if gesture == EntryGesture::Disabled && this.widget.control.entry.is_open() {
+            this.widget.control.entry.close();
+            this.ctx.request_render();
+        }
+    }
+
+    pub fn set_fine_modifier(this: &mut WidgetMut<'_, Self>, modifier: Modifiers) {
+        this.widget.fine_modifier = modifier;
+    }
+
+    pub fn set_fine_divisor(this: &mut WidgetMut<'_, Self>, divisor: f64) {
+        this.widget.fine_divisor = divisor.max(1.0);
+    }
+
+    fn normalized_of(&self, value: f64) -> f64 {
+        self.control.normalized_of(value)
+    }
+
+    fn value_at(&self, norm: f64) -> f64 {
+        self.control.value_at(norm)
+    }
+
+    /// Whether the configured fine modifier is held.
+    fn is_fine(&self, modifiers: Modifiers) -> bool {
+        !self.fine_modifier.is_empty() && modifiers.contains(self.fine_modifier)
+    }
+
+    /// Shape the tick labels and work out how much room they need.
+    fn rebuild_labels(&mut self, (font_ctx, layout_ctx): (&mut FontContext, &mut LayoutContext<BrushIndex>)) {
+        if !self.labels_dirty {
+            return;
+        }
+        self.labels_dirty = false;
+        self.tick_labels = self.ticks.iter()
+            .map(|t| t.label.as_deref().map(|l| layout_text((&mut *font_ctx, &mut *layout_ctx), l)))
+            .collect();
+        let extent = self.tick_labels.iter().flatten()
+            .map(|l| match self.orientation {
+                Orientation::Horizontal => l.height() as f64,
+                Orientation::Vertical => l.width() as f64,
+            })
+            .fold(0.0, f64::max);
+        self.label_extent = if extent > 0.0 { extent + LABEL_GAP } else { 0.0 };
+    }
+
+    fn track(&self, size: Size) -> Track {
+        Track::new(self.orientation, size, self.label_extent)
+    }
+}
+
+impl Widget for Slider {
+    type Action = ControlAction<f64>;
+
+    fn on_pointer_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &PointerEvent,
+    ) {
+        // Releases are handled even when disabled, so a drag always ends
+        if let PointerEvent::Up(..) | PointerEvent::Cancel(..) = event {
+            self.control.end_drag(ctx);
+            self.drag_start = None;
+            return;
+        }
+        if ctx.is_disabled() { return; }
+        match event {
+            PointerEvent::Down(PointerButtonEvent { state, .. }) => {
+                ctx.request_focus();
+                if self.control.entry.is_open() {
+                    // Clicking the control again abandons the typed text
+                    self.control.entry.close();
+                    ctx.request_render();
+                    return;
+                }
+                if self.control.entry_gesture.opens_on(state.count, state.modifiers) {
+                    self.control.entry.open();
+                    ctx.request_layout();
+                    ctx.request_render();
+                    return;
+                }
+                if state.count == 2 {
+                    // Double-click: reset to default
+                    self.control.value = self.control.default;
+                    ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureBegin);
+                    ctx.submit_action::<ControlAction<f64>>(ControlAction::Changed(self.control.value));
+                    ctx.submit_action::<ControlAction<f64>>(ControlAction::GestureEnd);
+                    ctx.request_render();
+                    if self.control.readout.invalidate() { ctx.request_layout(); }
+                    return;
+                }
+                self.control.begin_drag(ctx);
+                self.drag_start = Some(ctx.local_position(state.position));
+                self.drag_start_norm = self.normalized_of(self.control.value);
+            }
+            PointerEvent::Move(PointerUpdate { current, .. }) => {
+                if !ctx.is_active() {
+                    // Capture was lost without an Up or Cancel
+                    self.control.end_drag(ctx);
+                    self.drag_start = None;
+                    return;
+                }
+                if let Some(start) = self.drag_start {
+                    let pos = ctx.local_position(current.position);
+                    let delta = self.track(ctx.content_box_size()).normalized_delta(start, pos);
+                    let norm = (self.drag_start_norm + delta).clamp(0.0, 1.0);
+                    self.control.commit(ctx, self.value_at(norm));
+                }
+            }
+            PointerEvent::Scroll(PointerScrollEvent { delta, state, .. }) => {
+                let fine = self.is_fine(state.modifiers).then_some(self.fine_divisor);
+                self.control.on_scroll(ctx, delta, fine, None);
+            }
+            _ => {}
+        }
+    }
+
+    fn on_text_event(
+        &mut self,
+        ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        event: &TextEvent,
+    ) {
+        if ctx.is_disabled() { return; }
+        self.control.on_text_event(ctx, event, None);
+    }
+
+    fn accepts_pointer_interaction(&self) -> bool { true }
+    fn accepts_focus(&self) -> bool { true }
+    fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {}
+
+    fn update(&mut self, ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, event: &Update) {
+        match event {
+            Update::HoveredChanged(_) if self.control.readout.is_enabled() => ctx.request_render(),
+            Update::FocusChanged(false) if self.control.entry.is_open() => {
+                self.control.entry.close();
+                ctx.request_render();
+            }
+            Update::DisabledChanged(true) | Update::StashedChanged(true) => {
+                self.control.cancel_drag(ctx);
+                self.drag_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn measure(
+        &mut self,
+        ctx: &mut MeasureCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        axis: Axis,
+        len_req: LenReq,
+        _cross_length: Option<f64>,
+    ) -> f64 {
+        // Labels widen the slider across the track, so shape them here.
+        self.rebuild_labels(ctx.text_contexts());
+        track::measure(self.orientation, axis, len_req, self.length, THICKNESS + self.label_extent)
+    }
+
+    fn layout(&mut self, ctx: &mut LayoutCtx<'_>, _props: &PropertiesRef<'_>, _size: Size) {
+        self.rebuild_labels(ctx.text_contexts());
+        self.control.readout.rebuild(ctx.text_contexts(), self.control.value);
+        self.control.entry.rebuild(ctx.text_contexts());
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx<'_>, _props: &PropertiesRef<'_>, painter: &mut Painter<'_>) {
+        let size = ctx.content_box_size();
+        let track = self.track(size);
+        track.paint_groove(painter);
+
+        // Scale ticks and their labels
+        let mark_color = Color::from_rgb8(0x70, 0x70, 0x70);
+        let label_color = Color::from_rgb8(0x90, 0x90, 0x90);
+        let mark_stroke = Stroke::new(1.0);
+        let (min, max) = (self.control.min, self.control.max);
+        for (tick, label) in self.ticks.iter().zip(&self.tick_labels) {
+            if tick.value < min.min(max) || tick.value > min.max(max) {
+                continue;
+            }
+            let norm = self.normalized_of(tick.value);
+            track.paint_tick(
+                painter, norm,
+                -TRACK_WIDTH / 2.0 - 6.0, -TRACK_WIDTH / 2.0 - 2.0,
+                &mark_stroke, mark_color,
+            );
+            let Some(layout) = label else { continue; };
+            let (w, h) = (layout.width() as f64, layout.height() as f64);
+            let anchor = track.point(norm, -THICKNESS / 2.0);
+            // Labels sit beyond the grip's reach, kept inside the widget
+            // at the ends of the track.
+            let origin = match self.orientation {
+                Orientation::Horizontal => Point::new(
+                    (anchor.x - w / 2.0).clamp(0.0, (size.width - w).max(0.0)),
+                    anchor.y - LABEL_GAP - h,
+                ),
+                Orientation::Vertical => Point::new(
+                    anchor.x - LABEL_GAP - w,
+                    (anchor.y - h / 2.0).clamp(0.0, (size.height - h).max(0.0)),
+                ),
+            };
+            render_text(painter, Affine::translate(origin.to_vec2()), layout, &[label_color.into()], true);
+        }
+
+        track.paint_default_mark(painter, self.normalized_of(self.control.default));
+
+        // Lit fill from the fill origin (or minimum end) to the grip
+        let norm = self.normalized_of(self.control.value);
+        let fill_from = self.fill_from.map_or(0.0, |v| self.normalized_of(v));
+        track.paint_fill(painter, fill_from, norm, self.tint);
+
+        track.paint_grip(painter, norm, ctx.is_active(), ctx.is_hovered());
+
+        // Text entry field, or value readout while interacting
+        let grip_center = track.point(norm, 0.0);
+        if self.control.entry.is_open() {
+            self.control.entry.paint(painter, grip_center, self.tint);
+        } else if ctx.is_hovered() || ctx.is_active() {
+            self.control.readout.paint(painter, grip_center);
+        }
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Slider
+    }
+
+    fn accessibility(&mut self, _ctx: &mut AccessCtx<'_>, _props: &PropertiesRef<'_>, node: &mut Node) {
+        node.set_numeric_value(self.control.value);
+        node.set_min_numeric_value(self.control.min);
+        node.set_max_numeric_value(self.control.max);
+        // Unstepped sliders report the value distance of one arrow-key
+        // press at the current position, which varies along a taper.
+        let step = if self.control.step > 0.0 {
+            self.control.step
+        } else {
+            let n = self.normalized_of(self.control.value).min(1.0 - KEY_FRACTION);
+            (self.value_at(n + KEY_FRACTION) - self.value_at(n)).abs()
+        };
+        node.set_numeric_value_step(step);
+    }
+
+    fn children_ids(&self) -> SmallVec<[WidgetId; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self, id: WidgetId) -> tracing::Span {
+        trace_span!("Slider", id = id.trace())
+    }
+}
@@ -0,0 +1,582 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use std::sync::Arc;
+
+use xilem::masonry::accesskit::{Node, Role};
+use xilem::masonry::core::{
+    AccessCtx, BrushIndex, EventCtx, LayoutCtx, MeasureCtx, PaintCtx, PointerEvent, PropertiesMut,
+    PropertiesRef, RegisterCtx, Update, UpdateCtx, Widget, WidgetId, WidgetMut, render_text,
+};
+use xilem::masonry::imaging::Painter;
+use xilem::masonry::kurbo::{Affine, Axis, Point, Rect, RoundedRect, Size, Stroke};
+use xilem::masonry::layout::LenReq;
+use xilem::masonry::parley::Layout;
+use xilem::masonry::peniko::{
+    Blob, Color, Extend, Fill, ImageAlphaType, ImageBrush, ImageData, ImageFormat, ImageQuality,
+};
+
+use smallvec::SmallVec;
+use tracing::trace_span;
+
+use crate::fft::{self, ChannelMode, SpectrumAnalyzer, WindowFunction};
+use crate::widgets::readout::layout_text;
+use crate::widgets::scope::{SampleReader, ScopeReader, ScopeSource, claim_reader};
+
+/// Preferred size, used when the parent leaves the choice to the widget.
+const SPECTROGRAM_WIDTH: f64 = 240.0;
+const SPECTROGRAM_HEIGHT: f64 = 140.0;
+const MIN_WIDTH: f64 = 64.0;
+const MIN_HEIGHT: f64 = 40.0;
+const BORDER_RADIUS: f64 = 4.0;
+const PADDING: f64 = 2.0;
+/// Size of one painted cell: one STFT frame wide, one frequency band high.
+const CELL_WIDTH: f64 = 2.0;
+const CELL_HEIGHT: f64 = 2.0;
+const DEFAULT_FFT_SIZE: usize = 1024;
+const DEFAULT_SAMPLE_RATE: f64 = 48_000.0;
+const MIN_FREQ: f64 = 20.0;
+const LABEL_COLOR: Color = Color::from_rgb8(0xC0, 0xC0, 0xC0);
+
+/// Maps normalized intensity to color in a spectrogram.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum Colormap {
+    /// Black through purple and orange to pale yellow.
+    #[default]
+    Magma,
+    /// Dark blue through green to yellow.
+    Viridis,
+    /// Black to white.
+    Grayscale,
+}
+
+const MAGMA: [(u8, u8, u8); 9] = [
+    (0x00, 0x00, 0x04), (0x1C, 0x10, 0x44), (0x4F, 0x12, 0x7B),
+    (0x81, 0x25, 0x81), (0xB5, 0x36, 0x7A), (0xE5, 0x50, 0x64),
+    (0xFB, 0x87, 0x61), (0xFE, 0xC2, 0x87), (0xFC, 0xFD, 0xBF),
+];
+
+const VIRIDIS: [(u8, u8, u8); 9] = [
+    (0x44, 0x01, 0x54), (0x47, 0x2C, 0x7A), (0x3B, 0x51, 0x8B),
+    (0x2C, 0x71, 0x8E), (0x21, 0x90, 0x8D), (0x27, 0xAD, 0x81),
+    (0x5C, 0xC8, 0x63), (0xAA, 0xDC, 0x32), (0xFD, 0xE7, 0x25),
+];
+
+const GRAYSCALE: [(u8, u8, u8); 2] = [(0x00, 0x00, 0x00), (0xFF, 0xFF, 0xFF)];
+
+impl Colormap {
+    /// Color for `t` in `0..=1`, interpolated between the map's stops.
+    pub fn color(self, t: f32) -> Color {
+        let [r, g, b] = self.rgb8(t);
+        Color::from_rgb8(r, g, b)
+    }
+
+    fn rgb8(self, t: f32) -> [u8; 3] {
+        let stops: &[(u8, u8, u8)] = match self {
+            Colormap::Magma => &MAGMA,
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Grayscale => &GRAYSCALE,
+        };
+        let pos = t.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+        let i = (pos.floor() as usize).min(stops.len() - 2);
+        let frac = pos - i as f32;
+        let (a, b) = (stops[i], stops[i + 1]);
+        let mix = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * frac).round() as u8;
+        [mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2)]
+    }
+}
+
+/// A scrolling spectrogram (waterfall) of a [`ScopeSource`] signal.
+///
+/// Each animation frame the widget polls its source and runs a
+/// short-time Fourier transform over the new samples: one FFT per hop,
+/// each windowed over the last FFT-size samples. Every STFT frame
+/// becomes a column of cells, newest at the right edge, scrolling left
+/// as time passes. Frequency runs up the display on a log axis from
+/// 20 Hz to Nyquist, and level is colormapped over the dB range.
+///
+/// Multi-channel sources are mixed to mono by default, which cancels
+/// content that is out of phase between channels; [`ChannelMode::Max`]
+/// analyzes each channel and shows the loudest. With a gapless source (a
+/// [`ring_buffer`](crate::ring_buffer)) the STFT runs over the continuous
+/// stream. Blocks from a triple buffer are not contiguous, so each is
+/// analyzed on its own and must hold at least one FFT's worth of samples.
+///
+/// The cells are kept as an RGBA image with one pixel per cell, used as
+/// a ring of columns: each STFT frame writes only its own column, and
+/// the display is drawn as a single image.
+pub struct Spectrogram {
+    analyzer: SpectrumAnalyzer,
+    window: WindowFunction,
+    /// Samples between STFT frames; `None` for a quarter of the FFT size
+    hop: Option<usize>,
+    channel_mode: ChannelMode,
+    /// Incoming samples of each analyzed signal (one when mixing, one
+    /// per channel otherwise) still needed by the next FFT
+    pending: Vec<Vec<f32>>,
+    /// Samples since the last STFT frame
+    since_frame: usize,
+    /// Scratch analysis output, reused every frame
+    frame: Vec<f32>,
+    /// STFT frames reduced to display bands, `bands` levels in dB per
+    /// column, as a ring of `max_columns` columns
+    levels: Vec<f32>,
+    /// The same ring colormapped: RGBA, `max_columns` wide, `bands` high
+    pixels: Arc<Vec<u8>>,
+    /// Ring column the next STFT frame goes to
+    head: usize,
+    /// Columns written so far, up to `max_columns`
+    filled: usize,
+    /// Image over `pixels`, made on the first paint after a change
+    image: Option<ImageBrush>,
+    /// Number of columns and bands that fit the display
+    max_columns: usize,
+    bands: usize,
+    sample_rate: f64,
+    min_db: f32,
+    max_db: f32,
+    colormap: Colormap,
+    freq_labels: Vec<(f64, Layout<BrushIndex>)>,
+    labels_dirty: bool,
+    /// Optional shared source for polling new data during anim frames
+    source: Option<ScopeSource>,
+    /// The source's output, claimed on the first anim frame
+    reader: Option<ScopeReader>,
+    /// Another display held the source on the last try (already logged)
+    source_busy: bool,
+}
+
+impl Spectrogram {
+    pub fn new() -> Self {
+        let window = WindowFunction::default();
+        let analyzer = SpectrumAnalyzer::new(DEFAULT_FFT_SIZE, window);
+        let bins = analyzer.bins();
+        Self {
+            analyzer,
+            window,
+            hop: None,
+            channel_mode: ChannelMode::default(),
+            pending: Vec::new(),
+            since_frame: 0,
+            frame: vec![0.0; bins],
+            levels: Vec::new(),
+            pixels: Arc::new(Vec::new()),
+            head: 0,
+            filled: 0,
+            image: None,
+            max_columns: 0,
+            bands: 0,
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            min_db: -100.0,
+            max_db: 0.0,
+            colormap: Colormap::default(),
+            freq_labels: Vec::new(),
+            labels_dirty: true,
+            source: None,
+            reader: None,
+            source_busy: false,
+        }
+    }
+
+    /// Attach a shared source that the widget polls during animation frames.
+    pub fn with_source(mut self, source: ScopeSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Samples per FFT, rounded to a power of two in 64..=32768.
+    pub fn with_fft_size(mut self, size: usize) -> Self {
+        self.rebuild_analyzer(fft::fft_size(size), self.window);
+        self
+    }
+
+    pub fn with_window(mut self, window: WindowFunction) -> Self {
+        self.rebuild_analyzer(self.analyzer.size(), window);
+        self
+    }
+
+    /// How the channels of a multi-channel source are analyzed.
+    pub fn with_channel_mode(mut self, mode: ChannelMode) -> Self {
+        self.channel_mode = mode;
+        self
+    }
+
+    /// Samples between successive frames. Smaller hops scroll faster
+    /// and overlap more. Default: a quarter of the FFT size.
+    pub fn with_hop(mut self, hop: usize) -> Self {
+        self.hop = Some(hop);
+        self
+    }
+
+    pub fn with_sample_rate(mut self, sample_rate: f64) -> Self {
+        self.sample_rate = sample_rate;
+        self.labels_dirty = true;
+        self
+    }
+
+    /// Levels mapped onto the colormap, in dBFS. Default -100 to 0 dB.
+    pub fn with_db_range(mut self, min: f32, max: f32) -> Self {
+        self.min_db = min;
+        self.max_db = max;
+        self
+    }
+
+    pub fn with_colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    pub fn set_source(this: &mut WidgetMut<'_, Self>, source: ScopeSource) {
+        // Hand the old output back before claiming the new one
+        this.widget.reader = None;
+        this.widget.source_busy = false;
+        this.widget.source = Some(source);
+        this.widget.pending.clear();
+        this.ctx.request_anim_frame();
+    }
+
+    pub fn set_fft_size(this: &mut WidgetMut<'_, Self>, size: usize) {
+        let window = this.widget.window;
+        this.widget.rebuild_analyzer(fft::fft_size(size), window);
+        this.ctx.request_render();
+    }
+
+    pub fn set_window(this: &mut WidgetMut<'_, Self>, window: WindowFunction) {
+        let size = this.widget.analyzer.size();
+        this.widget.rebuild_analyzer(size, window);
+        this.ctx.request_render();
+    }
+
+    pub fn set_channel_mode(this: &mut WidgetMut<'_, Self>, mode: ChannelMode) {
+        this.widget.channel_mode = mode;
+        this.widget.pending.clear();
+        this.widget.since_frame = 0;
+    }
+
+    pub fn set_hop(this: &mut WidgetMut<'_, Self>, hop: Option<usize>) {
+        this.widget.hop = hop;
+    }
+
+    pub fn set_sample_rate(this: &mut WidgetMut<'_, Self>, sample_rate: f64) {
+        this.widget.sample_rate = sample_rate;
+        this.widget.labels_dirty = true;
+        // Bands now cover other frequencies
+        this.widget.clear_columns();
+        this.ctx.request_layout();
+        this.ctx.request_render();
+    }
+
+    pub fn set_db_range(this: &mut WidgetMut<'_, Self>, min: f32, max: f32) {
+        this.widget.min_db = min;
+        this.widget.max_db = max;
+        this.widget.recolor_columns();
+        this.ctx.request_render();
+    }
+
+    pub fn set_colormap(this: &mut WidgetMut<'_, Self>, colormap: Colormap) {
+        this.widget.colormap = colormap;
+        this.widget.recolor_columns();
+        this.ctx.request_render();
+    }
+
+    /// Replace the analyzer and drop frames computed with the old one.
+    fn rebuild_analyzer(&mut self, size: usize, window: WindowFunction) {
+        self.window = window;
+        self.analyzer = SpectrumAnalyzer::new(size, window);
+        self.frame = vec![0.0; self.analyzer.bins()];
+        self.clear_columns();
+    }
+
+    fn clear_columns(&mut self) {
+        self.head = 0;
+        self.filled = 0;
+        self.image = None;
+    }
+
+    fn hop(&self) -> usize {
+        let size = self.analyzer.size();
+        self.hop.unwrap_or(size / 4).clamp(1, size)
+    }
+
+    fn nyquist(&self) -> f64 {
+        (self.sample_rate / 2.0).max(MIN_FREQ * 2.0)
+    }
+
+    /// Frequency at normalized height `norm` (0 = bottom).
+    fn band_freq(&self, norm: f64) -> f64 {
+        MIN_FREQ * (self.nyquist() / MIN_FREQ).powf(norm)
+    }
+
+    /// Poll the source for new samples. Returns true if new columns
+    /// were added.
+    fn poll_source(&mut self) -> bool {
+        // Claim the output on first use; the samples are borrowed from
+        // the triple buffer while analyzing, so the reader is moved out
+        let mut reader = self.reader.take().or_else(|| {
+            self.source.as_ref().and_then(|s| claim_reader(s, &mut self.source_busy))
+        });
+        let mut added = false;
+        if let Some(r) = reader.as_mut() {
+            let channels = r.channels();
+            let gapless = r.is_gapless();
+            if let Some(samples) = r.poll() {
+                added = self.add_samples(samples, channels, gapless);
+            }
+        }
+        self.reader = reader;
+        added
+    }
+
+    /// Append interleaved samples, mixed to mono or per channel, and run
+    /// one STFT frame per completed hop. Returns true if new columns
+    /// were added.
+    fn add_samples(&mut self, samples: &[f32], channels: usize, gapless: bool) -> bool {
+        if !gapless {
+            // Joining blocks with gaps between them would put false edges
+            // into the FFT, so each block starts afresh
+            for pending in &mut self.pending {
+                pending.clear();
+            }
+            self.since_frame = 0;
+        }
+        let size = self.analyzer.size();
+        let hop = self.hop();
+        let mut added = false;
+        for frame in samples.chunks_exact(channels) {
+            fft::push_frame(frame, self.channel_mode, &mut self.pending);
+            self.since_frame += 1;
+            // All signals grow in step, so the first one stands for all
+            let len = self.pending[0].len();
+            if self.since_frame >= hop && len >= size {
+                self.since_frame = 0;
+                self.push_column();
+                added = true;
+            }
+            // Keep only the samples the next FFT can still use
+            if len >= size * 2 {
+                for pending in &mut self.pending {
+                    pending.drain(..size);
+                }
+            }
+        }
+        added
+    }
+
+    /// Analyze the latest FFT block into the next ring column.
+    fn push_column(&mut self) {
+        self.analyzer.max_magnitudes_db(&self.pending, &mut self.frame);
+        if self.bands == 0 || self.max_columns == 0 {
+            return;
+        }
+        let col = self.head;
+        let bin_hz = self.sample_rate / self.analyzer.size() as f64;
+        for band in 0..self.bands {
+            let lo = self.band_freq(band as f64 / self.bands as f64) / bin_hz;
+            let hi = self.band_freq((band + 1) as f64 / self.bands as f64) / bin_hz;
+            self.levels[col * self.bands + band] = fft::band_db(&self.frame, lo, hi);
+        }
+        self.color_column(col);
+        self.head = (col + 1) % self.max_columns;
+        self.filled = (self.filled + 1).min(self.max_columns);
+    }
+
+    /// Colormap one ring column into the pixels.
+    fn color_column(&mut self, col: usize) {
+        // Let go of the image first, so the pixels aren't copied on write
+        self.image = None;
+        let (width, bands) = (self.max_columns, self.bands);
+        let range = (self.max_db - self.min_db).max(1.0);
+        let pixels = Arc::make_mut(&mut self.pixels);
+        for band in 0..bands {
+            let t = (self.levels[col * bands + band] - self.min_db) / range;
+            let [r, g, b] = self.colormap.rgb8(t);
+            // Band 0 is the bottom row
+            let i = ((bands - 1 - band) * width + col) * 4;
+            pixels[i..i + 4].copy_from_slice(&[r, g, b, 0xFF]);
+        }
+    }
+
+    /// Recolor the written columns after the colormap or dB range changed.
+    fn recolor_columns(&mut self) {
+        for col in 0..self.filled {
+            self.color_column(col);
+        }
+    }
+
+    /// Match the cell grid to the display, keeping the newest columns
+    /// that still fit.
+    fn resize_grid(&mut self, size: Size) {
+        let columns = ((size.width - PADDING * 2.0) / CELL_WIDTH).floor().max(1.0) as usize;
+        let bands = ((size.height - PADDING * 2.0) / CELL_HEIGHT).floor().max(1.0) as usize;
+        if columns == self.max_columns && bands == self.bands {
+            return;
+        }
+        // Unroll the kept columns to the start of the new ring, oldest first
+        let keep = if bands == self.bands { self.filled.min(columns) } else { 0 };
+        let mut levels = vec![0.0; columns * bands];
+        for i in 0..keep {
+            let src = (self.head + self.max_columns - keep + i) % self.max_columns;
+            levels[i * bands..(i + 1) * bands]
+                .copy_from_slice(&self.levels[src * bands..(src + 1) * bands]);
+        }
+        self.levels = levels;
+        self.pixels = Arc::new(vec![0; columns * bands * 4]);
+        self.max_columns = columns;
+        self.bands = bands;
+        self.head = keep % columns;
+        self.filled = keep;
+        self.image = None;
+        self.recolor_columns();
+    }
+
+    fn rebuild_labels(&mut self, ctx: &mut LayoutCtx<'_>) {
+        if !self.labels_dirty {
+            return;
+        }
+        self.labels_dirty = false;
+        self.freq_labels.clear();
+        for (freq, text) in [(100.0, "100"), (1_000.0, "1k"), (10_000.0, "10k")] {
+            if freq < self.nyquist() {
+                self.freq_labels.push((freq, layout_text(ctx.text_contexts(), text)));
+            }
+        }
+    }
+}
+
+impl Widget for Spectrogram {
+    type Action = ();
+
+    fn on_pointer_event(
+        &mut self,
+        _ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        _event: &PointerEvent,
+    ) {
+    }
+
+    fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {}
+
+    fn on_anim_frame(
+        &mut self, ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, _interval: u64,
+    ) {
+        if self.source.is_some() {
+            if self.poll_source() {
+                ctx.request_render();
+            }
+            ctx.request_anim_frame();
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, event: &Update) {
+        if matches!(event, Update::WidgetAdded) && self.source.is_some() {
+            ctx.request_anim_frame();
+        }
+    }
+
+    fn measure(
+        &mut self,
+        _ctx: &mut MeasureCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        axis: Axis,
+        len_req: LenReq,
+        _cross_length: Option<f64>,
+    ) -> f64 {
+        let (preferred, min) = match axis {
+            Axis::Horizontal => (SPECTROGRAM_WIDTH, MIN_WIDTH),
+            Axis::Vertical => (SPECTROGRAM_HEIGHT, MIN_HEIGHT),
+        };
+        match len_req {
+            LenReq::MinContent => min,
+            LenReq::MaxContent => preferred,
+            LenReq::FitContent(space) => space.max(min),
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        size: Size,
+    ) {
+        self.resize_grid(size);
+        self.rebuild_labels(ctx);
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx<'_>, _props: &PropertiesRef<'_>, painter: &mut Painter<'_>) {
+        let size = ctx.content_box_size();
+        let rect = Rect::from_origin_size(Point::ZERO, size);
+        let rr = RoundedRect::from_rect(rect, BORDER_RADIUS);
+        painter.fill(rr, self.colormap.color(0.0)).fill_rule(Fill::NonZero).draw();
+
+        let draw = rect.inset(-PADDING);
+
+        // Cells, drawn as one image. It repeats sideways, so placing the
+        // newest ring column at the right edge puts the older ones,
+        // wrapped around, to its left.
+        if self.filled > 0 {
+            let (columns, bands) = (self.max_columns, self.bands);
+            let pixels = &self.pixels;
+            let image = self.image.get_or_insert_with(|| {
+                let data = ImageData {
+                    data: Blob::new(pixels.clone()),
+                    format: ImageFormat::Rgba8,
+                    alpha_type: ImageAlphaType::Alpha,
+                    width: columns as u32,
+                    height: bands as u32,
+                };
+                ImageBrush::new(data)
+                    .with_x_extend(Extend::Repeat)
+                    .with_quality(ImageQuality::Low)
+            });
+            let newest = (self.head + columns - 1) % columns;
+            let top = draw.y1 - bands as f64 * CELL_HEIGHT;
+            let origin = draw.x1 - (newest + 1) as f64 * CELL_WIDTH;
+            let transform = Affine::translate((origin, top))
+                * Affine::scale_non_uniform(CELL_WIDTH, CELL_HEIGHT);
+            let x0 = (draw.x1 - self.filled as f64 * CELL_WIDTH).max(draw.x0);
+            let shown = Rect::new(x0, top.max(draw.y0), draw.x1, draw.y1);
+            painter
+                .fill(shown, &*image)
+                .fill_rule(Fill::NonZero)
+                .brush_transform(transform)
+                .draw();
+        }
+
+        // Frequency labels up the left edge
+        for (freq, label) in &self.freq_labels {
+            let norm = (freq / MIN_FREQ).ln() / (self.nyquist() / MIN_FREQ).ln();
+            let y = draw.y1 - norm * draw.height() - label.height() as f64 / 2.0;
+            if y > draw.y0 && y + (label.height() as f64) < draw.y1 {
+                render_text(painter, Affine::translate((draw.x0 + 2.0, y)), label, &[LABEL_COLOR.into()], true);
+            }
+        }
+
+        // Border
+        painter.stroke(rr, &Stroke::new(0.5), Color::from_rgb8(0x40, 0x40, 0x40)).draw();
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Canvas
+    }
+
+    fn accessibility(
+        &mut self,
+        _ctx: &mut AccessCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        node: &mut Node,
+    ) {
+        node.set_description("Spectrogram display".to_string());
+    }
+
+    fn children_ids(&self) -> SmallVec<[WidgetId; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self, id: WidgetId) -> tracing::Span {
+        trace_span!("Spectrogram", id = id.trace())
+    }
+}
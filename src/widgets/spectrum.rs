@@ -0,0 +1,562 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use std::time::Duration;
+
+use xilem::masonry::accesskit::{Node, Role};
+use xilem::masonry::core::{
+    AccessCtx, BrushIndex, EventCtx, LayoutCtx, MeasureCtx, PaintCtx, PointerEvent, PropertiesMut,
+    PropertiesRef, RegisterCtx, Update, UpdateCtx, Widget, WidgetId, WidgetMut, render_text,
+};
+use xilem::masonry::imaging::Painter;
+use xilem::masonry::kurbo::{Affine, Axis, BezPath, Line, Point, Rect, RoundedRect, Size, Stroke};
+use xilem::masonry::layout::LenReq;
+use xilem::masonry::parley::Layout;
+use xilem::masonry::peniko::{Color, Fill};
+
+use smallvec::SmallVec;
+use tracing::trace_span;
+
+use crate::fft::{self, ChannelMode, SpectrumAnalyzer, WindowFunction};
+use crate::widgets::readout::layout_text;
+use crate::widgets::scope::{SampleReader, ScopeReader, ScopeSource, claim_reader};
+
+/// Preferred size, used when the parent leaves the choice to the widget.
+const SPECTRUM_WIDTH: f64 = 240.0;
+const SPECTRUM_HEIGHT: f64 = 140.0;
+const MIN_WIDTH: f64 = 64.0;
+const MIN_HEIGHT: f64 = 40.0;
+const BORDER_RADIUS: f64 = 4.0;
+const PADDING: f64 = 2.0;
+const DEFAULT_FFT_SIZE: usize = 2048;
+const DEFAULT_SAMPLE_RATE: f64 = 48_000.0;
+/// Lowest frequency the log axis may start at.
+const MIN_FREQ_FLOOR: f64 = 1.0;
+/// Peaks fall at this rate once their hold time is over.
+const PEAK_FALL_DB_PER_SEC: f32 = 20.0;
+const CURVE_COLOR: Color = Color::from_rgb8(0x00, 0xFF, 0x80);
+const PEAK_COLOR: Color = Color::from_rgb8(0xC0, 0xC0, 0xC0);
+const LABEL_COLOR: Color = Color::from_rgb8(0x50, 0x80, 0x50);
+
+/// A spectrum analyzer showing the magnitude of a [`ScopeSource`] signal
+/// over a logarithmic frequency axis.
+///
+/// Each animation frame the widget polls its source, keeps the latest
+/// FFT-size block of samples, windows it and runs an FFT. Multi-channel
+/// sources are mixed to mono by default, which cancels content that is
+/// out of phase between channels; [`ChannelMode::Max`] analyzes each
+/// channel and shows the loudest. The magnitudes are averaged over time
+/// according to the smoothing factor, and an optional peak-hold curve
+/// remembers recent maxima for the hold time before falling back.
+///
+/// The frequency axis runs logarithmically from 20 Hz to 20 kHz (capped
+/// at Nyquist), with decade grid lines. The level axis is in dBFS: a
+/// full-scale sine reads 0 dB.
+///
+/// Like the scope, it fills the space its parent offers.
+pub struct Spectrum {
+    analyzer: SpectrumAnalyzer,
+    window: WindowFunction,
+    channel_mode: ChannelMode,
+    /// Latest samples of each analyzed signal (one when mixing, one per
+    /// channel otherwise); at most one FFT block each
+    history: Vec<Vec<f32>>,
+    /// Per-bin magnitude in dB, averaged over time
+    magnitudes: Vec<f32>,
+    /// Per-bin held peak in dB
+    peaks: Vec<f32>,
+    /// Seconds since each bin's peak was set
+    peak_age: Vec<f32>,
+    /// Scratch analysis output, reused every frame
+    frame: Vec<f32>,
+    /// Weight of the previous magnitudes when averaging (0 = none)
+    smoothing: f32,
+    peak_hold: Option<Duration>,
+    sample_rate: f64,
+    min_freq: f64,
+    max_freq: f64,
+    min_db: f32,
+    max_db: f32,
+    color: Color,
+    bg_color: Color,
+    grid_color: Color,
+    /// Axis labels with their frequency / dB values, rebuilt in layout
+    freq_labels: Vec<(f64, Layout<BrushIndex>)>,
+    db_labels: Vec<(f32, Layout<BrushIndex>)>,
+    labels_dirty: bool,
+    /// Optional shared source for polling new data during anim frames
+    source: Option<ScopeSource>,
+    /// The source's output, claimed on the first anim frame
+    reader: Option<ScopeReader>,
+    /// Another display held the source on the last try (already logged)
+    source_busy: bool,
+}
+
+impl Spectrum {
+    pub fn new() -> Self {
+        let window = WindowFunction::default();
+        let analyzer = SpectrumAnalyzer::new(DEFAULT_FFT_SIZE, window);
+        let bins = analyzer.bins();
+        Self {
+            analyzer,
+            window,
+            channel_mode: ChannelMode::default(),
+            history: Vec::new(),
+            magnitudes: vec![f32::NEG_INFINITY; bins],
+            peaks: vec![f32::NEG_INFINITY; bins],
+            peak_age: vec![0.0; bins],
+            frame: vec![0.0; bins],
+            smoothing: 0.5,
+            peak_hold: None,
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            min_freq: 20.0,
+            max_freq: 20_000.0,
+            min_db: -90.0,
+            max_db: 0.0,
+            color: CURVE_COLOR,
+            bg_color: Color::from_rgb8(0x0A, 0x0A, 0x0A),
+            grid_color: Color::from_rgb8(0x20, 0x30, 0x20),
+            freq_labels: Vec::new(),
+            db_labels: Vec::new(),
+            labels_dirty: true,
+            source: None,
+            reader: None,
+            source_busy: false,
+        }
+    }
+
+    /// Attach a shared source that the widget polls during animation frames.
+    pub fn with_source(mut self, source: ScopeSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Samples per FFT block, rounded to a power of two in 64..=32768.
+    /// Larger sizes resolve low frequencies better but react more slowly.
+    pub fn with_fft_size(mut self, size: usize) -> Self {
+        self.rebuild_analyzer(fft::fft_size(size), self.window);
+        self
+    }
+
+    pub fn with_window(mut self, window: WindowFunction) -> Self {
+        self.rebuild_analyzer(self.analyzer.size(), window);
+        self
+    }
+
+    /// How the channels of a multi-channel source are analyzed.
+    pub fn with_channel_mode(mut self, mode: ChannelMode) -> Self {
+        self.channel_mode = mode;
+        self
+    }
+
+    /// Averaging between frames, from 0.0 (none) to just below 1.0 (very slow).
+    pub fn with_smoothing(mut self, smoothing: f32) -> Self {
+        self.smoothing = smoothing.clamp(0.0, 0.99);
+        self
+    }
+
+    /// Show a peak curve that holds each maximum for `hold` before falling.
+    pub fn with_peak_hold(mut self, hold: Option<Duration>) -> Self {
+        self.peak_hold = hold;
+        self
+    }
+
+    pub fn with_sample_rate(mut self, sample_rate: f64) -> Self {
+        self.sample_rate = sample_rate;
+        self.labels_dirty = true;
+        self
+    }
+
+    /// Displayed frequency range in Hz. Default 20 Hz to 20 kHz.
+    /// The range starts at 1 Hz or above and spans at least an octave.
+    pub fn with_freq_range(mut self, min: f64, max: f64) -> Self {
+        (self.min_freq, self.max_freq) = valid_freq_range(min, max);
+        self.labels_dirty = true;
+        self
+    }
+
+    /// Displayed level range in dBFS. Default -90 to 0 dB.
+    pub fn with_db_range(mut self, min: f32, max: f32) -> Self {
+        self.min_db = min;
+        self.max_db = max;
+        self.labels_dirty = true;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn set_source(this: &mut WidgetMut<'_, Self>, source: ScopeSource) {
+        // Hand the old output back before claiming the new one
+        this.widget.reader = None;
+        this.widget.source_busy = false;
+        this.widget.source = Some(source);
+        this.widget.history.clear();
+        this.ctx.request_anim_frame();
+    }
+
+    pub fn set_fft_size(this: &mut WidgetMut<'_, Self>, size: usize) {
+        let window = this.widget.window;
+        this.widget.rebuild_analyzer(fft::fft_size(size), window);
+        this.ctx.request_render();
+    }
+
+    pub fn set_window(this: &mut WidgetMut<'_, Self>, window: WindowFunction) {
+        let size = this.widget.analyzer.size();
+        this.widget.rebuild_analyzer(size, window);
+        this.ctx.request_render();
+    }
+
+    pub fn set_channel_mode(this: &mut WidgetMut<'_, Self>, mode: ChannelMode) {
+        this.widget.channel_mode = mode;
+        this.widget.history.clear();
+    }
+
+    pub fn set_smoothing(this: &mut WidgetMut<'_, Self>, smoothing: f32) {
+        this.widget.smoothing = smoothing.clamp(0.0, 0.99);
+    }
+
+    pub fn set_peak_hold(this: &mut WidgetMut<'_, Self>, hold: Option<Duration>) {
+        this.widget.peak_hold = hold;
+        this.widget.peaks.fill(f32::NEG_INFINITY);
+        this.ctx.request_render();
+    }
+
+    pub fn set_sample_rate(this: &mut WidgetMut<'_, Self>, sample_rate: f64) {
+        this.widget.sample_rate = sample_rate;
+        this.widget.labels_dirty = true;
+        this.ctx.request_layout();
+        this.ctx.request_render();
+    }
+
+    pub fn set_freq_range(this: &mut WidgetMut<'_, Self>, min: f64, max: f64) {
+        (this.widget.min_freq, this.widget.max_freq) = valid_freq_range(min, max);
+        this.widget.labels_dirty = true;
+        this.ctx.request_layout();
+        this.ctx.request_render();
+    }
+
+    pub fn set_db_range(this: &mut WidgetMut<'_, Self>, min: f32, max: f32) {
+        this.widget.min_db = min;
+        this.widget.max_db = max;
+        this.widget.labels_dirty = true;
+        this.ctx.request_layout();
+        this.ctx.request_render();
+    }
+
+    pub fn set_color(this: &mut WidgetMut<'_, Self>, color: Color) {
+        this.widget.color = color;
+        this.ctx.request_render();
+    }
+
+    /// Replace the analyzer and reset everything measured with the old one.
+    fn rebuild_analyzer(&mut self, size: usize, window: WindowFunction) {
+        self.window = window;
+        self.analyzer = SpectrumAnalyzer::new(size, window);
+        let bins = self.analyzer.bins();
+        self.magnitudes = vec![f32::NEG_INFINITY; bins];
+        self.peaks = vec![f32::NEG_INFINITY; bins];
+        self.peak_age = vec![0.0; bins];
+        self.frame = vec![0.0; bins];
+    }
+
+    /// Poll the source and append its samples, mixed to mono or per
+    /// channel. Returns true if new data arrived.
+    fn poll_source(&mut self) -> bool {
+        // Claim the reader on first use; it is moved out while its
+        // samples are appended
+        let mut reader = self.reader.take().or_else(|| {
+            self.source.as_ref().and_then(|s| claim_reader(s, &mut self.source_busy))
+        });
+        let mut arrived = false;
+        if let Some(r) = reader.as_mut() {
+            let channels = r.channels();
+            if let Some(samples) = r.poll() {
+                for frame in samples.chunks_exact(channels) {
+                    fft::push_frame(frame, self.channel_mode, &mut self.history);
+                }
+                arrived = true;
+            }
+        }
+        self.reader = reader;
+        let size = self.analyzer.size();
+        for history in &mut self.history {
+            if history.len() > size {
+                let drain = history.len() - size;
+                history.drain(..drain);
+            }
+        }
+        arrived
+    }
+
+    /// Analyze the latest block and fold it into the averaged magnitudes.
+    fn analyze(&mut self) {
+        self.analyzer.max_magnitudes_db(&self.history, &mut self.frame);
+        let keep = self.smoothing;
+        for (avg, &db) in self.magnitudes.iter_mut().zip(&self.frame) {
+            *avg = if avg.is_finite() { keep * *avg + (1.0 - keep) * db } else { db };
+        }
+    }
+
+    /// Advance the peak-hold curve by `dt` seconds.
+    fn update_peaks(&mut self, dt: f32) {
+        let Some(hold) = self.peak_hold else {
+            return;
+        };
+        let hold = hold.as_secs_f32();
+        for ((peak, age), &db) in self.peaks.iter_mut().zip(&mut self.peak_age).zip(&self.magnitudes) {
+            if db >= *peak {
+                *peak = db;
+                *age = 0.0;
+            } else {
+                *age += dt;
+                if *age > hold {
+                    *peak = (*peak - PEAK_FALL_DB_PER_SEC * dt).max(db);
+                }
+            }
+        }
+    }
+
+    /// Upper end of the frequency axis, capped at Nyquist.
+    fn top_freq(&self) -> f64 {
+        self.max_freq.min(self.sample_rate / 2.0).max(self.min_freq * 2.0)
+    }
+
+    /// Horizontal position of `freq` within the drawing area.
+    fn freq_to_x(&self, freq: f64, draw: Rect) -> f64 {
+        let norm = (freq / self.min_freq).ln() / (self.top_freq() / self.min_freq).ln();
+        draw.x0 + norm * draw.width()
+    }
+
+    fn x_to_freq(&self, x: f64, draw: Rect) -> f64 {
+        let norm = (x - draw.x0) / draw.width();
+        self.min_freq * (self.top_freq() / self.min_freq).powf(norm)
+    }
+
+    fn db_to_y(&self, db: f32, draw: Rect) -> f64 {
+        let range = (self.max_db - self.min_db).max(1.0);
+        let norm = ((db - self.min_db) / range).clamp(0.0, 1.0) as f64;
+        draw.y1 - norm * draw.height()
+    }
+
+    /// Spacing of the dB grid, a few lines over the range.
+    fn db_step(&self) -> f32 {
+        match self.max_db - self.min_db {
+            r if r <= 36.0 => 6.0,
+            r if r <= 72.0 => 12.0,
+            _ => 24.0,
+        }
+    }
+
+    fn rebuild_labels(&mut self, ctx: &mut LayoutCtx<'_>) {
+        if !self.labels_dirty {
+            return;
+        }
+        self.labels_dirty = false;
+        self.freq_labels.clear();
+        self.db_labels.clear();
+        for (freq, text) in [(100.0, "100"), (1_000.0, "1k"), (10_000.0, "10k")] {
+            if freq > self.min_freq && freq < self.top_freq() {
+                self.freq_labels.push((freq, layout_text(ctx.text_contexts(), text)));
+            }
+        }
+        let step = self.db_step();
+        let mut db = (self.max_db / step).floor() * step;
+        while db > self.min_db {
+            self.db_labels.push((db, layout_text(ctx.text_contexts(), &format!("{db:.0}"))));
+            db -= step;
+        }
+    }
+
+    /// Outline of a per-bin curve across the drawing area, one point per pixel.
+    fn curve_path(&self, values: &[f32], draw: Rect) -> BezPath {
+        let bin_hz = self.sample_rate / self.analyzer.size() as f64;
+        let columns = draw.width().ceil().max(1.0) as usize;
+        let mut path = BezPath::new();
+        for i in 0..=columns {
+            let x = draw.x0 + i as f64;
+            // Bins covered by this pixel; several at the top end of a log axis
+            let b0 = self.x_to_freq(x - 0.5, draw) / bin_hz;
+            let b1 = self.x_to_freq(x + 0.5, draw) / bin_hz;
+            let db = fft::band_db(values, b0, b1);
+            let p = Point::new(x.min(draw.x1), self.db_to_y(db, draw));
+            if i == 0 {
+                path.move_to(p);
+            } else {
+                path.line_to(p);
+            }
+        }
+        path
+    }
+}
+
+impl Widget for Spectrum {
+    type Action = ();
+
+    fn on_pointer_event(
+        &mut self,
+        _ctx: &mut EventCtx<'_>,
+        _props: &mut PropertiesMut<'_>,
+        _event: &PointerEvent,
+    ) {
+    }
+
+    fn register_children(&mut self, _ctx: &mut RegisterCtx<'_>) {}
+
+    fn on_anim_frame(
+        &mut self, ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, interval: u64,
+    ) {
+        if self.source.is_none() {
+            return;
+        }
+        let fresh = self.poll_source();
+        if fresh {
+            self.analyze();
+        }
+        // Held peaks keep falling between blocks
+        if fresh || self.peak_hold.is_some() {
+            self.update_peaks(interval as f32 * 1e-9);
+            ctx.request_render();
+        }
+        ctx.request_anim_frame();
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx<'_>, _props: &mut PropertiesMut<'_>, event: &Update) {
+        if matches!(event, Update::WidgetAdded) && self.source.is_some() {
+            ctx.request_anim_frame();
+        }
+    }
+
+    fn measure(
+        &mut self,
+        _ctx: &mut MeasureCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        axis: Axis,
+        len_req: LenReq,
+        _cross_length: Option<f64>,
+    ) -> f64 {
+        let (preferred, min) = match axis {
+            Axis::Horizontal => (SPECTRUM_WIDTH, MIN_WIDTH),
+            Axis::Vertical => (SPECTRUM_HEIGHT, MIN_HEIGHT),
+        };
+        match len_req {
+            LenReq::MinContent => min,
+            LenReq::MaxContent => preferred,
+            LenReq::FitContent(space) => space.max(min),
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        _size: Size,
+    ) {
+        self.rebuild_labels(ctx);
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx<'_>, _props: &PropertiesRef<'_>, painter: &mut Painter<'_>) {
+        let size = ctx.content_box_size();
+        let rect = Rect::from_origin_size(Point::ZERO, size);
+        let rr = RoundedRect::from_rect(rect, BORDER_RADIUS);
+        painter.fill(rr, self.bg_color).fill_rule(Fill::NonZero).draw();
+
+        let draw = rect.inset(-PADDING);
+        let grid_stroke = Stroke::new(0.5);
+
+        // Frequency grid: decades strong, the multiples in between faint
+        let mut decade = 10.0_f64.powf(self.min_freq.log10().floor());
+        while decade < self.top_freq() {
+            for m in 1..10 {
+                let freq = decade * m as f64;
+                if freq <= self.min_freq || freq >= self.top_freq() {
+                    continue;
+                }
+                let x = self.freq_to_x(freq, draw);
+                let color = if m == 1 { self.grid_color } else { self.grid_color.with_alpha(0.5) };
+                painter.stroke(
+                    Line::new(Point::new(x, draw.y0), Point::new(x, draw.y1)),
+                    &grid_stroke, color,
+                ).draw();
+            }
+            decade *= 10.0;
+        }
+
+        // Level grid
+        for (db, _) in &self.db_labels {
+            let y = self.db_to_y(*db, draw);
+            painter.stroke(
+                Line::new(Point::new(draw.x0, y), Point::new(draw.x1, y)),
+                &grid_stroke, self.grid_color,
+            ).draw();
+        }
+
+        // Labels: dB down the left edge, frequencies along the bottom
+        for (db, label) in &self.db_labels {
+            let y = self.db_to_y(*db, draw) + 1.0;
+            if y + (label.height() as f64) < draw.y1 {
+                render_text(painter, Affine::translate((draw.x0 + 2.0, y)), label, &[LABEL_COLOR.into()], true);
+            }
+        }
+        for (freq, label) in &self.freq_labels {
+            let (w, h) = (label.width() as f64, label.height() as f64);
+            let x = self.freq_to_x(*freq, draw) + 2.0;
+            if x + w < draw.x1 {
+                render_text(painter, Affine::translate((x, draw.y1 - h - 1.0)), label, &[LABEL_COLOR.into()], true);
+            }
+        }
+
+        if self.magnitudes.iter().any(|m| m.is_finite()) {
+            // Magnitude curve, filled down to the bottom of the display
+            let curve = self.curve_path(&self.magnitudes, draw);
+            let mut area = curve.clone();
+            area.line_to(Point::new(draw.x1, draw.y1));
+            area.line_to(Point::new(draw.x0, draw.y1));
+            area.close_path();
+            painter.fill(&area, self.color.with_alpha(0.2)).fill_rule(Fill::NonZero).draw();
+            painter.stroke(&curve, &Stroke::new(1.0), self.color).draw();
+        }
+
+        if self.peak_hold.is_some() && self.peaks.iter().any(|p| p.is_finite()) {
+            let peaks = self.curve_path(&self.peaks, draw);
+            painter.stroke(&peaks, &Stroke::new(0.75), PEAK_COLOR.with_alpha(0.7)).draw();
+        }
+
+        // Border
+        painter.stroke(rr, &Stroke::new(0.5), Color::from_rgb8(0x40, 0x40, 0x40)).draw();
+    }
+
+    fn accessibility_role(&self) -> Role {
+        Role::Canvas
+    }
+
+    fn accessibility(
+        &mut self,
+        _ctx: &mut AccessCtx<'_>,
+        _props: &PropertiesRef<'_>,
+        node: &mut Node,
+    ) {
+        node.set_description("Spectrum analyzer display".to_string());
+    }
+
+    fn children_ids(&self) -> SmallVec<[WidgetId; 16]> {
+        SmallVec::new()
+    }
+
+    fn make_trace_span(&self, id: WidgetId) -> tracing::Span {
+        trace_span!("Spectrum", id = id.trace())
+    }
+}
+
+/// Keep a frequency range usable on a log axis: starting at 1 Hz or
+/// above (a zero start would never reach the first decade) and rising.
+fn valid_freq_range(min: f64, max: f64) -> (f64, f64) {
+    let min = min.max(MIN_FREQ_FLOOR);
+    (min, max.max(min * 2.0))
+}
@@ -0,0 +1,206 @@
+//! This file is part of the xilem_synth_widgets project.
+//! (c) 2026 by Jacek Wisniowski
+//!
+//! This project was released as open source under the
+//! Apache License, Version 2.0: http://www.apache.org/licenses/LICENSE-2.0
+//! (compatible with the Xilem licence).
+
+use xilem::masonry::imaging::Painter;
+use xilem::masonry::kurbo::{Axis, Cap, Line, Point, Rect, RoundedRect, Size, Stroke};
+use xilem::masonry::layout::LenReq;
+use xilem::masonry::peniko::{Color, Fill};
+
+use crate::widgets::level_meter::Orientation;
+
+/// Size across the track.
+pub(crate) const THICKNESS: f64 = 32.0;
+/// Preferred size along the track.
+pub(crate) const DEFAULT_LENGTH: f64 = 140.0;
+pub(crate) const TRACK_WIDTH: f64 = 4.0;
+const GRIP_WIDTH: f64 = 24.0;
+const GRIP_HEIGHT: f64 = 14.0;
+const GRIP_RADIUS: f64 = 3.0;
+const TRACK_MARGIN: f64 = GRIP_HEIGHT / 2.0 + 4.0;
+/// Shortest length that still leaves some travel for the grip.
+pub(crate) const MIN_LENGTH: f64 = 2.0 * TRACK_MARGIN + 24.0;
+
+/// Geometry and drawing shared by the fader and slider: a groove with a
+/// grip travelling along it, in either orientation.
+///
+/// Positions are given along the track (0..1 from the minimum end) and
+/// across it, as an offset from the track's centre line. Offsets below
+/// zero are on the scale side: left of a vertical track, above a
+/// horizontal one.
+pub(crate) struct Track {
+    orientation: Orientation,
+    size: Size,
+    /// Distance of the centre line from the scale-side edge.
+    center: f64,
+}
+
+impl Track {
+    /// A track centred across the widget, after setting aside `inset`
+    /// on the scale side (e.g. for labels).
+    pub(crate) fn new(orientation: Orientation, size: Size, inset: f64) -> Self {
+        let cross = match orientation {
+            Orientation::Horizontal => size.height,
+            Orientation::Vertical => size.width,
+        };
+        Self { orientation, size, center: inset + (cross - inset).max(0.0) / 2.0 }
+    }
+
+    /// Size of the widget along the track.
+    pub(crate) fn length(&self) -> f64 {
+        match self.orientation {
+            Orientation::Horizontal => self.size.width,
+            Orientation::Vertical => self.size.height,
+        }
+    }
+
+    /// Start and end of the grip travel, measured from the minimum end.
+    fn range(&self) -> (f64, f64) {
+        (TRACK_MARGIN, self.length() - TRACK_MARGIN)
+    }
+
+    /// Distance of a normalized position from the minimum end.
+    fn pos(&self, norm: f64) -> f64 {
+        let (start, end) = self.range();
+        start + norm * (end - start)
+    }
+
+    /// Normalized position of a local point, clamped to the track.
+    pub(crate) fn normalized_at(&self, p: Point) -> f64 {
+        let (start, end) = self.range();
+        ((self.along(p) - start) / (end - start)).clamp(0.0, 1.0)
+    }
+
+    /// Normalized distance covered by moving from `from` to `to`.
+    pub(crate) fn normalized_delta(&self, from: Point, to: Point) -> f64 {
+        let (start, end) = self.range();
+        (self.along(to) - self.along(from)) / (end - start)
+    }
+
+    /// Distance of a local point along the track, from the minimum end.
+    fn along(&self, p: Point) -> f64 {
+        match self.orientation {
+            Orientation::Horizontal => p.x,
+            Orientation::Vertical => self.size.height - p.y,
+        }
+    }
+
+    /// Local point at normalized position `norm`, `offset` across the track.
+    pub(crate) fn point(&self, norm: f64, offset: f64) -> Point {
+        self.point_at(self.pos(norm), offset)
+    }
+
+    fn point_at(&self, along: f64, offset: f64) -> Point {
+        let across = self.center + offset;
+        match self.orientation {
+            Orientation::Horizontal => Point::new(along, across),
+            Orientation::Vertical => Point::new(across, self.size.height - along),
+        }
+    }
+
+    fn rect_at(&self, along0: f64, offset0: f64, along1: f64, offset1: f64) -> Rect {
+        Rect::from_points(self.point_at(along0, offset0), self.point_at(along1, offset1))
+    }
+
+    /// Whether a local point lies on the grip at normalized position `norm`.
+    pub(crate) fn grip_contains(&self, norm: f64, p: Point) -> bool {
+        let pos = self.pos(norm);
+        self.rect_at(pos - GRIP_HEIGHT / 2.0, -GRIP_WIDTH / 2.0, pos + GRIP_HEIGHT / 2.0, GRIP_WIDTH / 2.0)
+            .contains(p)
+    }
+
+    pub(crate) fn paint_groove(&self, painter: &mut Painter<'_>) {
+        let (start, end) = self.range();
+        let rect = self.rect_at(start, -TRACK_WIDTH / 2.0, end, TRACK_WIDTH / 2.0);
+        let rr = RoundedRect::from_rect(rect, TRACK_WIDTH / 2.0);
+        painter.fill(rr, Color::from_rgb8(0x30, 0x30, 0x30)).fill_rule(Fill::NonZero).draw();
+    }
+
+    /// Light the groove between two normalized positions.
+    pub(crate) fn paint_fill(&self, painter: &mut Painter<'_>, from: f64, to: f64, color: Color) {
+        if (to - from).abs() < f64::EPSILON {
+            return;
+        }
+        let rect = self.rect_at(
+            self.pos(from), -TRACK_WIDTH / 2.0 + 0.5,
+            self.pos(to), TRACK_WIDTH / 2.0 - 0.5,
+        );
+        painter.fill(rect, color).fill_rule(Fill::NonZero).draw();
+    }
+
+    /// Draw a tick across the track at `norm`, from offset `near` to `far`.
+    pub(crate) fn paint_tick(
+        &self,
+        painter: &mut Painter<'_>,
+        norm: f64,
+        near: f64,
+        far: f64,
+        stroke: &Stroke,
+        color: Color,
+    ) {
+        painter.stroke(Line::new(self.point(norm, near), self.point(norm, far)), stroke, color).draw();
+    }
+
+    /// Prominent marks either side of the groove, used for the default value.
+    pub(crate) fn paint_default_mark(&self, painter: &mut Painter<'_>, norm: f64) {
+        let color = Color::from_rgb8(0xB0, 0xB0, 0xB0);
+        let stroke = Stroke::new(1.5);
+        let inner = TRACK_WIDTH / 2.0 + 1.0;
+        let outer = TRACK_WIDTH / 2.0 + 8.0;
+        self.paint_tick(painter, norm, -outer, -inner, &stroke, color);
+        self.paint_tick(painter, norm, inner, outer, &stroke, color);
+    }
+
+    pub(crate) fn paint_grip(&self, painter: &mut Painter<'_>, norm: f64, active: bool, hovered: bool) {
+        let pos = self.pos(norm);
+        let grip_rect = self.rect_at(
+            pos - GRIP_HEIGHT / 2.0, -GRIP_WIDTH / 2.0,
+            pos + GRIP_HEIGHT / 2.0, GRIP_WIDTH / 2.0,
+        );
+        let grip_rr = RoundedRect::from_rect(grip_rect, GRIP_RADIUS);
+        let grip_color = if active {
+            Color::from_rgb8(0x90, 0x90, 0x90)
+        } else if hovered {
+            Color::from_rgb8(0x80, 0x80, 0x80)
+        } else {
+            Color::from_rgb8(0x6A, 0x6A, 0x6A)
+        };
+        painter.fill(grip_rr, grip_color).fill_rule(Fill::NonZero).draw();
+        painter.stroke(grip_rr, &Stroke::new(1.0), Color::from_rgb8(0xA0, 0xA0, 0xA0)).draw();
+
+        // Grip lines (texture)
+        let line_stroke = Stroke::new(0.5).with_caps(Cap::Butt);
+        let line_color = Color::from_rgb8(0x50, 0x50, 0x50);
+        for i in [-2.0, 0.0, 2.0] {
+            painter.stroke(
+                Line::new(
+                    self.point_at(pos + i, -GRIP_WIDTH / 2.0 + 4.0),
+                    self.point_at(pos + i, GRIP_WIDTH / 2.0 - 4.0),
+                ),
+                &line_stroke, line_color,
+            ).draw();
+        }
+    }
+}
+
+/// Measure a track widget: `cross` across the track, and along it the
+/// `preferred` length, shrunk to fit the offered space.
+pub(crate) fn measure(
+    orientation: Orientation,
+    axis: Axis,
+    len_req: LenReq,
+    preferred: f64,
+    cross: f64,
+) -> f64 {
+    match (orientation, axis) {
+        (Orientation::Horizontal, Axis::Vertical) | (Orientation::Vertical, Axis::Horizontal) => cross,
+        _ => match len_req {
+            LenReq::MinContent => MIN_LENGTH,
+            LenReq::MaxContent => preferred,
+            LenReq::FitContent(space) => preferred.min(space).max(MIN_LENGTH),
+        },
+    }
+}